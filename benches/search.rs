@@ -0,0 +1,71 @@
+//! Search index benchmark over a synthetic 50k entry list.
+//!
+//! Run with `cargo bench --bench search`.
+
+#[path = "../src/search.rs"]
+#[allow(dead_code)]
+mod search;
+
+use std::hint::black_box;
+use std::time::Instant;
+use search::SearchIndex;
+
+const ENTRIES: usize = 50_000;
+const WORDS: &[&str] = &[
+    "fire", "fox", "thunder", "bird", "gimp", "term", "code", "files", "music", "player",
+    "video", "edit", "office", "writer", "calc", "system", "monitor", "network", "manager", "shell",
+];
+
+fn names() -> Vec<String> {
+    (0..ENTRIES)
+        .map(|i| {
+            let a = WORDS[i % WORDS.len()];
+            let b = WORDS[(i / WORDS.len()) % WORDS.len()];
+            format!("{}{} {}", capitalize(a), b, i)
+        })
+        .collect()
+}
+
+fn capitalize(s: &str) -> String {
+    let mut chars = s.chars();
+    match chars.next() {
+        Some(c) => c.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+fn bench<F: FnMut()>(label: &str, iterations: u32, mut f: F) {
+    let start = Instant::now();
+    for _ in 0..iterations {
+        f();
+    }
+    let per_iter = start.elapsed() / iterations;
+    println!("{:<40} {:>12?} / iter", label, per_iter);
+}
+
+fn main() {
+    let names = names();
+
+    bench("build index (50k)", 10, || {
+        black_box(SearchIndex::new(names.iter().map(|n| (n.as_str(), &[][..])), true));
+    });
+
+    let index = SearchIndex::new(names.iter().map(|n| (n.as_str(), &[][..])), true);
+
+    // Simulate typing: every keystroke is a new query and must be scored
+    let typed = ["t", "th", "thu", "thun", "thund", "thunde", "thunder", "thunderb"];
+    bench("keystroke (uncached query)", 5, || {
+        for q in typed {
+            black_box(index.search(q));
+        }
+    });
+
+    // Simulate redraws between keystrokes: same query, served from cache
+    index.search("thunderb");
+    bench("redraw (cached query)", 10_000, || {
+        black_box(index.search("thunderb"));
+    });
+
+    let results = index.search("thunderb");
+    println!("{} matches for \"thunderb\"", results.len());
+}
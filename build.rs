@@ -0,0 +1,54 @@
+//! Turns the embedded emoji table (data/emoji.txt) into a static array for the emoji source
+
+use std::env;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+
+fn main() {
+    println!("cargo:rerun-if-changed=data/emoji.txt");
+
+    let table = fs::read_to_string("data/emoji.txt").expect("data/emoji.txt is readable");
+    let mut out = String::from("&[\n");
+    let mut group = String::new();
+    let mut open = false;
+
+    for line in table.lines() {
+        if let Some(name) = line.strip_prefix("# group: ") {
+            group = name.to_string();
+            continue;
+        }
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        // A skin-tone variant of the emoji above
+        if let Some(variant) = line.strip_prefix('\t') {
+            let (character, tones) = variant.split_once('\t').expect("variant has character and tones");
+            writeln!(out, "        ({:?}, {:?}),", character, tones).unwrap();
+            continue;
+        }
+
+        if open {
+            out.push_str("    ] },\n");
+        }
+        let mut fields = line.split('\t');
+        let character = fields.next().unwrap();
+        let name = fields.next().unwrap_or_else(|| panic!("no name for {}", character));
+        let keywords: Vec<&str> = fields.next().unwrap_or("").split(',').filter(|k| !k.is_empty()).collect();
+        writeln!(
+            out,
+            "    Emoji {{ character: {:?}, name: {:?}, group: {:?}, keywords: &{:?}, tones: &[",
+            character, name, group, keywords
+        )
+        .unwrap();
+        open = true;
+    }
+    if open {
+        out.push_str("    ] },\n");
+    }
+    out.push_str("]\n");
+
+    let path = Path::new(&env::var("OUT_DIR").unwrap()).join("emoji.rs");
+    fs::write(path, out).unwrap();
+}
@@ -1,9 +1,12 @@
+use std::collections::HashMap;
 use std::fs;
-use std::path::Path;
+use std::rc::Rc;
+use std::sync::mpsc::{Receiver, TryRecvError};
 use std::time::Instant;
 use crate::config::DstlConfig;
-use fuzzy_matcher::FuzzyMatcher;
-use fuzzy_matcher::skim::SkimMatcherV2;
+use crate::search::{Match, SearchIndex};
+use crate::sources::{self, Context, DesktopApps, Source};
+use crate::web::Shortcut;
 use tui_input::Input;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -19,15 +22,12 @@ pub enum Mode {
     DualPane,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum SinglePaneMode {
-    Dmenu,       // load apps from PATH (dmenu style)
-    DesktopApps, // load .desktop apps
-}
-
 pub struct App {
     pub mode: Mode,
-    pub single_pane_mode: SinglePaneMode,
+    /// Enabled sources, in the order the cycle key steps through them
+    pub sources: Vec<Rc<dyn Source>>,
+    /// Index into `sources` of the one shown in single-pane mode
+    pub source: usize,
     pub should_quit: bool,
     pub input: Input,
     pub cursor_visible: bool,
@@ -38,16 +38,29 @@ pub struct App {
     pub selected_category: usize,
     pub selected_app: usize,
     pub focus: Focus,
-    pub app_to_launch: Option<String>,
+    pub app_to_launch: Option<AppEntry>,
+    /// Sway window that had focus before dstl opened, for sway modes
+    pub previous_window: Option<i64>,
+    /// Entries derived from the query itself (e.g. a calculator result), shown above the apps
+    pub pinned: Vec<AppEntry>,
+    /// Entries the current source offers for the query, like creating what it names, shown
+    /// below the apps
+    pub offers: Vec<AppEntry>,
+    /// Entry waiting for the user to answer its confirmation prompt
+    pub confirming: Option<AppEntry>,
     pub config: DstlConfig,
-    fuzzy_matcher: SkimMatcherV2,
+    index: SearchIndex,
+    app_positions: HashMap<String, usize>,
+    /// Entries still being found in the background by the current source
+    stream: Option<Rc<Receiver<Vec<AppEntry>>>>,
 }
 
 impl Clone for App {
     fn clone(&self) -> Self {
         Self {
             mode: self.mode,
-            single_pane_mode: self.single_pane_mode,
+            sources: self.sources.clone(),
+            source: self.source,
             should_quit: self.should_quit,
             input: self.input.clone(),
             cursor_visible: true,
@@ -59,8 +72,14 @@ impl Clone for App {
             selected_app: self.selected_app,
             focus: self.focus,
             app_to_launch: self.app_to_launch.clone(),
+            previous_window: self.previous_window,
+            pinned: self.pinned.clone(),
+            offers: self.offers.clone(),
+            confirming: self.confirming.clone(),
             config: self.config.clone(),
-            fuzzy_matcher: SkimMatcherV2::default(),
+            index: self.index.clone(),
+            app_positions: self.app_positions.clone(),
+            stream: self.stream.clone(),
         }
     }
 }
@@ -69,7 +88,8 @@ impl std::fmt::Debug for App {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("App")
             .field("mode", &self.mode)
-            .field("single_pane_mode", &self.single_pane_mode)
+            .field("sources", &self.sources.iter().map(|s| s.name()).collect::<Vec<_>>())
+            .field("source", &self.source)
             .field("should_quit", &self.should_quit)
             .field("input", &self.input)
             .field("cursor_visible", &self.cursor_visible)
@@ -81,21 +101,78 @@ impl std::fmt::Debug for App {
             .field("selected_app", &self.selected_app)
             .field("focus", &self.focus)
             .field("app_to_launch", &self.app_to_launch)
+            .field("previous_window", &self.previous_window)
+            .field("pinned", &self.pinned)
+            .field("offers", &self.offers)
+            .field("confirming", &self.confirming)
             .field("config", &self.config)
-            .field("fuzzy_matcher", &"SkimMatcherV2")
+            .field("index", &self.index)
+            .field("streaming", &self.stream.is_some())
             .finish()
     }
 }
 
-#[derive(Debug, Clone)]
+/// What activating an entry does
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum Action {
+    /// Run `exec`, in a terminal if the entry needs one
+    #[default]
+    Launch,
+    /// Hand text back to the user, printed to stdout or copied (see `output_mode`)
+    Output(String),
+    /// Print text to stdout for the calling script, whatever `output_mode` says
+    Print(String),
+    /// Run a shell command in the configured terminal, e.g. a shell in some directory
+    Terminal(String),
+    /// Open a URL with the default web browser
+    Url(String),
+    /// Run a command over sway IPC, e.g. `[con_id=4] focus`
+    Sway(String),
+    /// Pass this back to the current script, which may reply with a new list
+    Script(String),
+    /// Ask the current source for a follow-up list, e.g. the skin tones of an emoji
+    Submenu(String),
+    /// Send a signal to a process: (pid, name it was listed with, signal)
+    Signal(i32, String, i32),
+}
+
+#[derive(Debug, Clone, Default)]
 pub struct AppEntry {
     pub name: String,
     pub category: String,
     pub exec: String,
     pub terminal: bool,
+    pub keywords: Vec<String>,
+    /// Glyph shown before the name
+    pub icon: Option<String>,
+    /// Secondary text shown after the name, e.g. the user and address of an SSH host
+    pub description: Option<String>,
+    /// Name of the source the entry came from, shown as a badge in combi mode
+    pub source: String,
+    pub action: Action,
+    /// Secondary action, triggered with Alt+Enter
+    pub alt_action: Option<Action>,
+    /// Question to confirm with y/n before the entry is activated
+    pub confirm: Option<String>,
+    /// Question to confirm before the alternate action
+    pub alt_confirm: Option<String>,
 }
 
 impl AppEntry {
+    /// Name as shown in the list, with the icon and description if there are any
+    pub fn display_name(&self) -> String {
+        let name = match &self.icon {
+            Some(icon) => format!("{}  {}", icon, self.name),
+            None => self.name.clone(),
+        };
+        match &self.description {
+            Some(description) => format!("{}  ({})", name, description),
+            None => name,
+        }
+    }
+
+    /// Guess whether a desktop or PATH program needs a terminal. Other sources set `terminal`
+    /// themselves, and their URLs and paths would trip this up.
     pub fn needs_terminal(&self) -> bool {
         self.category == "CLI"
             || self.exec.contains("bash")
@@ -108,36 +185,70 @@ impl AppEntry {
 }
 
 impl App {
-    /// Initialize the app with specified single pane mode and start mode
-    pub fn new(single_pane_mode: SinglePaneMode, start_mode: Mode, config: &DstlConfig) -> Self {
-        let (categories, apps, mode, focus) = match start_mode {
-            Mode::SinglePane => {
-                let (cats, apps) = Self::load_for_mode(single_pane_mode);
-                (cats, apps, Mode::SinglePane, Focus::Apps)
-            }
-            Mode::DualPane => {
-                let (cats, apps) = Self::load_desktop_apps();
-                (cats, apps, Mode::DualPane, Focus::Categories)
+    /// Initialize the app showing the named source (see `sources::build`) and start mode.
+    /// A source that isn't enabled in the config is put first in the cycle.
+    pub fn new(start_source: &str, start_mode: Mode, config: &DstlConfig) -> Self {
+        let mut sources = sources::enabled(config);
+        let source = match sources.iter().position(|s| s.name() == start_source) {
+            Some(index) => index,
+            None => {
+                if let Some(extra) = sources::build(start_source, config) {
+                    sources.insert(0, extra);
+                }
+                0
             }
         };
+        if sources.is_empty() {
+            sources.push(Rc::new(DesktopApps));
+        }
+
+        // Look this up before anything else touches focus
+        let previous_window = if sources.iter().any(|s| s.uses_previous_window()) {
+            crate::sway::Client::connect()
+                .and_then(|mut client| client.get_previous_window())
+                .ok()
+                .flatten()
+                .map(|w| w.id)
+        } else {
+            None
+        };
+
+        let focus = match start_mode {
+            Mode::SinglePane => Focus::Apps,
+            Mode::DualPane => Focus::Categories,
+        };
 
         let mut app = Self {
-            mode,
-            single_pane_mode,
+            mode: start_mode,
+            sources,
+            source,
             should_quit: false,
             input: Input::default(),
             cursor_visible: true,
             cursor_last_toggle: Instant::now(),
-            categories,
-            apps,
+            categories: Vec::new(),
+            apps: Vec::new(),
             recent_apps: Vec::new(),
             selected_category: 0,
             selected_app: 0,
             focus,
             app_to_launch: None,
+            previous_window,
+            pinned: Vec::new(),
+            offers: Vec::new(),
+            confirming: None,
             config: config.clone(),
-            fuzzy_matcher: SkimMatcherV2::default(),
+            index: SearchIndex::new([], config.smart_case),
+            app_positions: HashMap::new(),
+            stream: None,
         };
+        match start_mode {
+            Mode::SinglePane => app.load_source(),
+            Mode::DualPane => {
+                let (categories, apps) = DesktopApps.load(&app.context());
+                app.set_entries(categories, apps);
+            }
+        }
 
         // Load recent apps from disk
         let _ = app.load_recent();
@@ -168,6 +279,13 @@ impl App {
         let _ = self.save_recent();
     }
 
+    /// Count a launch towards the recent apps list, if the source it came from keeps one
+    pub fn record_launch(&mut self, entry: &AppEntry) {
+        if self.current_source().records_recent(entry) {
+            self.add_to_recent(entry.name.clone());
+        }
+    }
+
     /// Save recent apps to disk
     pub fn save_recent(&self) -> std::io::Result<()> {
         let config_dir = dirs::cache_dir()
@@ -197,46 +315,264 @@ impl App {
         Ok(())
     }
 
-    pub fn visible_apps(&self) -> Vec<&AppEntry> {
-        let query_string = self.query();
-        let query = &query_string;
+    /// Replace the loaded entries and rebuild the search index over them
+    pub fn set_entries(&mut self, categories: Vec<String>, apps: Vec<AppEntry>) {
+        self.index = SearchIndex::new(
+            apps.iter().map(|a| (a.name.as_str(), a.keywords.as_slice())),
+            self.config.smart_case,
+        );
+        self.app_positions = apps.iter()
+            .enumerate()
+            .rev() // First occurrence wins for duplicate names
+            .map(|(i, a)| (a.name.clone(), i))
+            .collect();
+        self.categories = categories;
+        self.apps = apps;
+        self.stream = None;
+    }
 
-        // Start with all apps
-        let mut apps: Vec<&AppEntry> = if query.is_empty() {
-            self.apps.iter().collect()
-        } else {
-            // Fuzzy match when searching
-            let mut matched: Vec<(&AppEntry, i64)> = self.apps.iter()
-                .filter_map(|a| self.matches_search(&a.name, query).map(|score| (a, score)))
-                .collect();
-            matched.sort_by(|a, b| b.1.cmp(&a.1));
-            matched.into_iter().map(|(a, _)| a).collect()
+    /// Load the current source's entries and start any background search it does
+    fn load_source(&mut self) {
+        let source = Rc::clone(&self.sources[self.source]);
+        let (categories, apps) = source.load(&self.context());
+        self.set_entries(categories, apps);
+        self.stream = source.stream(&self.context()).map(Rc::new);
+    }
+
+    /// Append entries the current source has found in the background since the last poll
+    pub fn poll_stream(&mut self) {
+        let Some(stream) = &self.stream else {
+            return;
+        };
+
+        let mut apps = Vec::new();
+        let finished = loop {
+            match stream.try_recv() {
+                Ok(batch) => apps.extend(batch),
+                Err(TryRecvError::Empty) => break false,
+                Err(TryRecvError::Disconnected) => break true,
+            }
+        };
+        if finished {
+            self.stream = None;
+        }
+        if apps.is_empty() {
+            return;
+        }
+
+        self.index.extend(apps.iter().map(|a| (a.name.as_str(), a.keywords.as_slice())));
+        for (i, app) in apps.iter().enumerate() {
+            self.app_positions.entry(app.name.clone()).or_insert(self.apps.len() + i);
+            if !self.categories.contains(&app.category) {
+                self.categories.push(app.category.clone());
+            }
+        }
+        self.apps.extend(apps);
+    }
+
+    /// All apps matching the current query, best first (cached per query). A category prefix the
+    /// source splits off the query, like `3:` for man pages, narrows the matches to that category.
+    pub fn search_results(&self) -> Rc<[Match]> {
+        let (category, text) = self.current_source().split_query(self.input.value());
+        let results = self.index.search(text);
+        match category {
+            Some(category) => results.iter()
+                .filter(|m| self.apps[m.index].category.starts_with(category))
+                .copied()
+                .collect(),
+            None => results,
+        }
+    }
+
+    /// Whether nothing matched the query and the results are typo-tolerant guesses instead
+    pub fn is_did_you_mean(&self) -> bool {
+        self.search_results().first().is_some_and(|m| m.typo)
+    }
+
+    /// Indices of recent apps that still exist, most recent first
+    fn recent_indices(&self) -> Vec<usize> {
+        self.recent_apps.iter()
+            .filter_map(|name| self.app_positions.get(name).copied())
+            .collect()
+    }
+
+    /// Indices of the apps in the list currently on screen. Drawing and navigation both use this,
+    /// so the selection always refers to what is displayed.
+    pub fn visible_apps(&self) -> Vec<usize> {
+        match self.mode {
+            Mode::SinglePane => self.single_pane_apps(),
+            Mode::DualPane => self.category_apps(self.selected_category),
+        }
+    }
+
+    /// The entry under the selection, if any. Pinned entries come first and offers last.
+    pub fn selected_entry(&self) -> Option<&AppEntry> {
+        if let Some(entry) = self.pinned.get(self.selected_app) {
+            return Some(entry);
+        }
+        let row = self.selected_app - self.pinned.len();
+        let visible = self.visible_apps();
+        match visible.get(row) {
+            Some(&i) => Some(&self.apps[i]),
+            None => self.offers.get(row - visible.len()),
+        }
+    }
+
+    /// Number of selectable rows in the list on screen, pinned entries and offers included
+    pub fn row_count(&self) -> usize {
+        self.pinned.len() + self.visible_apps().len() + self.offers.len()
+    }
+
+    /// Display names of the rows in the list on screen: pinned entries, apps, then offers.
+    /// Sources that merge others get a leading column with each row's source.
+    pub fn row_names(&self) -> Vec<String> {
+        let rows: Vec<&AppEntry> = self.pinned.iter()
+            .chain(self.visible_apps().into_iter().map(|i| &self.apps[i]))
+            .chain(&self.offers)
+            .collect();
+
+        if !self.current_source().shows_badges() {
+            return rows.into_iter().map(AppEntry::display_name).collect();
+        }
+
+        let width = rows.iter().map(|e| e.source.chars().count()).max().unwrap_or(0);
+        rows.into_iter()
+            .map(|e| format!("{:<width$}  {}", e.source, e.display_name(), width = width))
+            .collect()
+    }
+
+    /// Recompute the entries derived from the query. Call whenever the input changes.
+    pub fn refresh_pinned(&mut self) {
+        self.pinned.clear();
+        self.offers.clear();
+
+        if let Some(entry) = self.calculator_entry() {
+            self.pinned.push(entry);
+        }
+        if let Some(entry) = self.web_entry() {
+            self.pinned.push(entry);
+        }
+
+        if self.mode == Mode::SinglePane {
+            let source = Rc::clone(&self.sources[self.source]);
+            self.offers = source.offers(self.input.value(), &self.apps, &self.context());
+        }
+    }
+
+    /// A calculator result row, if the query has the calculator prefix or is an expression
+    fn calculator_entry(&self) -> Option<AppEntry> {
+        let calc = &self.config.calc;
+        if !calc.enabled {
+            return None;
+        }
+
+        let query = self.input.value().trim();
+        let value = match query.strip_prefix(calc.prefix.as_str()) {
+            Some(expr) if !calc.prefix.is_empty() => crate::calc::evaluate(expr).ok()?,
+            _ => crate::calc::evaluate_if_expression(query)?,
         };
+        let result = crate::calc::format_result(value);
+
+        Some(AppEntry {
+            name: format!("{}  {}", crate::icons::CALCULATOR, result),
+            category: "Calculator".to_string(),
+            action: Action::Output(result),
+            ..Default::default()
+        })
+    }
+
+    /// A row opening the query as a URL, or searching the web for it after an engine's bang
+    fn web_entry(&self) -> Option<AppEntry> {
+        if !self.config.web.enabled {
+            return None;
+        }
+
+        let (name, url, icon) = match crate::web::parse(self.input.value(), &self.config.web.engines)? {
+            Shortcut::Url(url) => (format!("Open {}", url), url, crate::icons::WEB),
+            Shortcut::Search { engine, terms, url } => {
+                (format!("Search {} for {}", engine, terms), url, crate::icons::WEB_SEARCH)
+            }
+        };
+        Some(AppEntry {
+            name,
+            category: "Web".to_string(),
+            icon: Some(icon.to_string()),
+            action: Action::Url(url),
+            ..Default::default()
+        })
+    }
+
+    fn single_pane_apps(&self) -> Vec<usize> {
+        let results = self.search_results();
+        let apps = results.iter().map(|m| m.index);
 
         // If recent_first and not searching, reorder
-        if query.is_empty() && self.config.recent_first && !self.recent_apps.is_empty() {
-            let mut recent_list = Vec::new();
-            let mut seen = std::collections::HashSet::new();
-
-            // Add recent apps first (must exist in apps)
-            for recent_name in &self.recent_apps {
-                if let Some(app) = apps.iter().find(|a| a.name == *recent_name) {
-                    recent_list.push(*app);
-                    seen.insert(recent_name.clone());
-                }
+        if self.input.value().is_empty() && self.config.recent_first && !self.recent_apps.is_empty() {
+            let recent = self.recent_indices();
+            let mut seen = vec![false; self.apps.len()];
+            for &i in &recent {
+                seen[i] = true;
             }
 
-            // Add remaining apps
-            for app in apps {
-                if !seen.contains(&app.name) {
-                    recent_list.push(app);
-                }
+            // Recent apps first, then the remaining apps
+            recent.iter()
+                .copied()
+                .chain(apps.filter(|&i| !seen[i]))
+                .collect()
+        } else {
+            apps.collect()
+        }
+    }
+
+    /// Indices of matching apps in the given category, best first
+    pub fn category_apps(&self, category: usize) -> Vec<usize> {
+        let Some(cat_name) = self.categories.get(category) else {
+            return Vec::new();
+        };
+
+        if cat_name == "Recent" {
+            let recent = self.recent_indices();
+            if self.input.value().is_empty() {
+                return recent;
             }
+            self.search_results()
+                .iter()
+                .filter(|m| recent.contains(&m.index))
+                .map(|m| m.index)
+                .collect()
+        } else {
+            self.search_results()
+                .iter()
+                .filter(|m| &self.apps[m.index].category == cat_name)
+                .map(|m| m.index)
+                .collect()
+        }
+    }
 
-            apps = recent_list;
+    /// Indices of categories that contain at least one app matching the current query
+    pub fn matching_categories(&self) -> Vec<usize> {
+        if self.input.value().is_empty() {
+            return (0..self.categories.len()).collect();
         }
 
-        apps
+        let results = self.search_results();
+        let matched: std::collections::HashSet<&str> = results.iter()
+            .map(|m| self.apps[m.index].category.as_str())
+            .collect();
+        let recent = self.recent_indices();
+
+        self.categories
+            .iter()
+            .enumerate()
+            .filter(|(_, cat_name)| {
+                if *cat_name == "Recent" {
+                    results.iter().any(|m| recent.contains(&m.index))
+                } else {
+                    matched.contains(cat_name.as_str())
+                }
+            })
+            .map(|(idx, _)| idx)
+            .collect()
     }
 
     pub fn update_cursor_blink(&mut self) {
@@ -267,19 +603,17 @@ impl App {
     pub fn toggle_mode(&mut self) {
         match self.mode {
             Mode::SinglePane => {
-                let (categories, apps) = Self::load_desktop_apps();
-                self.categories = categories;
-                self.apps = apps;
+                // Only desktop apps have a dual-pane view
+                let (categories, apps) = DesktopApps.load(&self.context());
+                self.set_entries(categories, apps);
                 self.mode = Mode::DualPane;
                 
                 // Keep leftmost pane focused when switching to DualPane
                 self.focus = Focus::Categories;
             }
             Mode::DualPane => {
-                let (categories, apps) = Self::load_for_mode(self.single_pane_mode);
-                self.categories = categories;
-                self.apps = apps;
                 self.mode = Mode::SinglePane;
+                self.load_source();
                 
                 // Leftmost pane in SinglePane is Apps
                 self.focus = Focus::Apps;
@@ -291,360 +625,109 @@ impl App {
         self.selected_app = 0;
     }
 
-    /// Toggle dmenu mode (PATH executables) vs Desktop Apps (SinglePane)
-    pub fn toggle_dmenu_mode(&mut self) {
-        self.single_pane_mode = match self.single_pane_mode {
-            SinglePaneMode::Dmenu => SinglePaneMode::DesktopApps,
-            SinglePaneMode::DesktopApps => SinglePaneMode::Dmenu,
-        };
+    /// Step to the next enabled source (SinglePane)
+    pub fn cycle_source(&mut self) {
+        self.source = (self.source + 1) % self.sources.len();
 
         // Always switch to SinglePane to show the new list
         self.mode = Mode::SinglePane;
-        let (categories, apps) = Self::load_for_mode(self.single_pane_mode);
-        self.categories = categories;
-        self.apps = apps;
+        self.load_source();
+        self.input = Input::default();
+        self.refresh_pinned();
         self.selected_app = 0;
         self.selected_category = 0;
         self.focus = Focus::Apps;
     }
 
-    /// Check if an app matches the search query using fuzzy matching (case-insensitive)
-    pub fn matches_search(&self, app_name: &str, query: &str) -> Option<i64> {
-        if query.is_empty() {
-            return Some(0); // Empty query matches everything
-        }
-
-        let app_name_lower = app_name.to_lowercase();
-        let query_lower = query.to_lowercase();
-
-        // Exact prefix match gets highest priority
-        if app_name_lower.starts_with(&query_lower) {
-            return Some(i64::MAX); // Push to top
+    /// The source of the list currently on screen
+    fn current_source(&self) -> Rc<dyn Source> {
+        match self.mode {
+            Mode::SinglePane => Rc::clone(&self.sources[self.source]),
+            Mode::DualPane => Rc::new(DesktopApps),
         }
-
-        // Fuzzy match otherwise
-        self.fuzzy_matcher.fuzzy_match(&app_name_lower, &query_lower)
     }
 
-    /// Load apps based on the single pane mode
-    fn load_for_mode(mode: SinglePaneMode) -> (Vec<String>, Vec<AppEntry>) {
-        let (categories, mut apps) = match mode {
-            SinglePaneMode::DesktopApps => Self::load_desktop_apps(),
-            SinglePaneMode::Dmenu => Self::load_from_path("/usr/bin"),
-        };
-        
-        // Sort apps alphabetically for single pane mode
-        apps.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
-        
-        (categories, apps)
+    fn context(&self) -> Context<'_> {
+        Context { config: &self.config, previous_window: self.previous_window }
     }
 
-    /// Load .desktop apps from local and system directories
-    fn load_desktop_apps() -> (Vec<String>, Vec<AppEntry>) {
-        use std::collections::{HashMap, HashSet};
-
-        let mut apps = Vec::new();
-        let mut category_map: HashMap<String, Vec<String>> = HashMap::new();
-        let mut seen_apps: HashSet<String> = HashSet::new();
-        let mut seen_files: HashSet<String> = HashSet::new(); // Track processed .desktop files
-
-        let home = std::env::var("HOME").unwrap_or_else(|_| String::from("/home"));
-        let local_dir = format!("{}/.local/share/applications", home);
-
-        let paths = vec![local_dir, "/usr/share/applications".to_string()];
-
-        // Get current desktop environment once
-        let current_desktops: Vec<String> = std::env::var("XDG_CURRENT_DESKTOP")
-            .or_else(|_| std::env::var("DESKTOP_SESSION"))
-            .unwrap_or_default()
-            .split(':')
-            .map(|s| s.trim().to_lowercase())
-            .collect();
-
-        for dir in paths {
-            if let Ok(entries) = fs::read_dir(&dir) {
-                for entry in entries.flatten() {
-                    let path = entry.path();
-                    if path.extension().and_then(|s| s.to_str()) != Some("desktop") {
-                        continue;
-                    }
-
-                    // Get the base filename to check for duplicates across directories
-                    let filename = path.file_name()
-                        .and_then(|n| n.to_str())
-                        .unwrap_or("")
-                        .to_string();
-                    
-                    // Skip if we've already processed this .desktop file from another directory
-                    if seen_files.contains(&filename) {
-                        continue;
-                    }
-                    seen_files.insert(filename.clone());
-
-                    if let Ok(content) = fs::read_to_string(&path) {
-                        let mut name = None;
-                        let mut generic_name = None;
-                        let mut exec = None;
-                        let mut categories = None;
-                        let mut no_display = false;
-                        let mut terminal = false;
-                        let mut only_show_in: Option<Vec<String>> = None;
-                        let mut not_show_in: Option<Vec<String>> = None;
-                        let mut in_desktop_entry = false;
-
-                        for line in content.lines() {
-                            let line = line.trim();
-                            
-                            // Track sections
-                            if line.starts_with('[') {
-                                in_desktop_entry = line == "[Desktop Entry]";
-                                continue;
-                            }
-                            
-                            // Only parse inside [Desktop Entry] section
-                            if !in_desktop_entry {
-                                continue;
-                            }
-                            
-                            // Parse key=value pairs
-                            if let Some((key, value)) = line.split_once('=') {
-                                // Skip localized entries like Name[af]=, Comment[de]=, etc.
-                                if key.contains('[') {
-                                    continue;
-                                }
-                                
-                                let key = key.trim();
-                                let value = value.trim();
-                                
-                                match key {
-                                    "Name" => name = Some(value.to_string()),
-                                    "GenericName" => generic_name = Some(value.to_string()),
-                                    "Exec" => exec = Some(value.to_string()),
-                                    "Categories" => categories = Some(value.to_string()),
-                                    "NoDisplay" => no_display = value == "true",
-                                    "Hidden" => no_display = no_display || value == "true",
-                                    "Terminal" => terminal = value == "true",
-                                    "OnlyShowIn" => {
-                                        only_show_in = Some(
-                                            value.split(';')
-                                                .map(|s| s.trim())
-                                                .filter(|s| !s.is_empty())
-                                                .map(|s| s.to_string())
-                                                .collect()
-                                        );
-                                    }
-                                    "NotShowIn" => {
-                                        not_show_in = Some(
-                                            value.split(';')
-                                                .map(|s| s.trim())
-                                                .filter(|s| !s.is_empty())
-                                                .map(|s| s.to_string())
-                                                .collect()
-                                        );
-                                    }
-                                    _ => {}
-                                }
-                            }
-                        }
-
-                        // Skip apps marked as NoDisplay or Hidden
-                        if no_display {
-                            continue;
-                        }
-                        
-                        // Use Name, or fallback to GenericName
-                        let name = name.or(generic_name);
-
-                        // Check OnlyShowIn - skip if specified and current desktop not in list
-                        if let Some(desktops) = &only_show_in {
-                            let allowed = desktops.iter()
-                                .any(|d| current_desktops.contains(&d.to_lowercase()));
-                            
-                            if !allowed {
-                                continue;
-                            }
-                        }
-
-                        // Check NotShowIn - skip if current desktop is in list
-                        if let Some(desktops) = &not_show_in {
-                            let blocked = desktops.iter()
-                                .any(|d| current_desktops.contains(&d.to_lowercase()));
-                            if blocked {
-                                continue;
-                            }
-                        }
-
-                        if let (Some(name), Some(exec)) = (name, exec) {
-                            // Skip if we've already seen this app name
-                            if seen_apps.contains(&name) {
-                                continue;
-                            }
-                            seen_apps.insert(name.clone());
-                            
-                            // Determine grouped category             
-                            let cat_group = if let Some(cats) = categories {
-                                Self::group_category(&cats, &name)
-                            } else {
-                                Self::group_category("", &name)
-                            };
-
-                            // Clean up Exec field codes (%f, %F, %u, %U, etc.)
-                            let exec_clean = Self::clean_exec(&exec);
-
-                            apps.push(AppEntry {
-                                name: name.clone(),
-                                category: cat_group.clone(),
-                                exec: exec_clean,
-                                terminal,
-                            });
-
-                            category_map
-                                .entry(cat_group)
-                                .or_default()
-                                .push(name);
-                        }
-                    }
-                }
-            }
+    /// Activate the selected entry, with its alternate action if `alternate` is set. Returns
+    /// true if dstl should quit and perform `app_to_launch`, false if the menu stays open.
+    pub fn activate_selected(&mut self, alternate: bool) -> bool {
+        let Some(mut entry) = self.selected_entry().cloned() else {
+            return false;
+        };
+        if alternate {
+            let Some(alt_action) = entry.alt_action.take() else {
+                return false;
+            };
+            entry.action = alt_action;
+            entry.confirm = entry.alt_confirm.take();
         }
 
-        // Build the list of grouped categories with Recent first
-        let mut categories = vec!["Recent".to_string()];
-        
-        let category_order = vec![
-            "Utilities", "Development", "Network", "Audio/Video", "Graphics",
-            "System", "Office", "Games", "Education", "Settings"
-        ];
-        categories.extend(
-            category_order
-                .into_iter()
-                .filter(|c| category_map.contains_key(*c))
-                .map(|s| s.to_string())
-        );
+        if entry.confirm.is_some() {
+            self.confirming = Some(entry);
+            return false;
+        }
 
-        (categories, apps)
+        self.activate(entry)
     }
 
-    /// Clean desktop entry Exec field by removing field codes
-    fn clean_exec(exec: &str) -> String {
-        // Remove field codes like %f, %F, %u, %U, %d, %D, %n, %N, %i, %c, %k, %v, %m
-        let mut result = exec.to_string();
-        let field_codes = ["%f", "%F", "%u", "%U", "%d", "%D", "%n", "%N", "%i", "%c", "%k", "%v", "%m"];
-        for code in &field_codes {
-            result = result.replace(code, "");
+    /// Answer the pending confirmation prompt. Returns true if dstl should quit.
+    pub fn answer_confirmation(&mut self, accept: bool) -> bool {
+        match self.confirming.take() {
+            Some(entry) if accept => self.activate(entry),
+            _ => false,
         }
-        result.trim().to_string()
     }
 
-    /// Map raw .desktop categories to simplified groupings 
-    fn group_category(raw: &str, app_name: &str) -> String {
-        let raw = raw.to_lowercase();
-
-        // Special case for Claw
-        if app_name.to_lowercase() == "claw" {
-            return "Utilities".to_string();
+    fn activate(&mut self, entry: AppEntry) -> bool {
+        if let Some((categories, apps)) = self.current_source().select(&entry, &self.context()) {
+            self.set_entries(categories, apps);
+            self.input = Input::default();
+            self.refresh_pinned();
+            self.selected_app = 0;
+            self.selected_category = 0;
+            return false;
         }
 
-        if app_name.to_lowercase() == "rofi" {
-            return "Utilities".to_string();
-        }
+        self.app_to_launch = Some(entry);
+        self.should_quit = true;
+        true
+    }
+}
 
-        // Prioritize "game" before "network"
-        if raw.contains("game") { "Games".to_string() }
-        else if raw.contains("utility") { "Utilities".to_string() }
-        else if raw.contains("development") { "Development".to_string() }
-        else if raw.contains("network") { "Network".to_string() }
-        else if raw.contains("audio") || raw.contains("video") { "Audio/Video".to_string() }
-        else if raw.contains("graphics") || raw.contains("2dgraphics") || raw.contains("3dgraphics") {
-            "Graphics".to_string()
-        }
-        else if raw.contains("system") { "System".to_string() }
-        else if raw.contains("office") { "Office".to_string() }
-        else if raw.contains("education") { "Education".to_string() }
-        else if raw.contains("settings") { "Settings".to_string() }
-        else { "Utilities".to_string() }
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::test_config;
+
+    /// An app on the named source listing `names` instead of what the source would load
+    fn app_with(source: &str, names: &[&str]) -> App {
+        let mut app = App::new(source, Mode::SinglePane, &test_config());
+        let apps = names.iter()
+            .map(|name| AppEntry { name: name.to_string(), action: Action::Print(name.to_string()), ..Default::default() })
+            .collect();
+        app.set_entries(Vec::new(), apps);
+        app
     }
 
-    /// Load executables from a directory (dmenu style)
-    fn load_from_path<P: AsRef<Path>>(path: P) -> (Vec<String>, Vec<AppEntry>) {
-        let mut apps = Vec::new();
-        let gui_bins = Self::get_known_gui_binaries();
-
-        if let Ok(entries) = fs::read_dir(path) {
-            for entry in entries.flatten() {
-                let path = entry.path();
-                if path.is_file() {
-                    if let Some(name) = path.file_name().and_then(|s| s.to_str()) {
-                        let is_gui = gui_bins.contains(name);
-                        apps.push(AppEntry {
-                            name: name.to_string(),
-                            category: "CLI".to_string(),
-                            exec: name.to_string(),
-                            terminal: !is_gui,
-                        });
-                    }
-                }
-            }
-        }
+    fn search(app: &mut App, query: &str) {
+        app.input = Input::new(query.to_string());
+        app.refresh_pinned();
+        app.selected_app = 0;
+    }
 
-        // Dmenu-style uses CLI category for consistency
-        (vec!["CLI".to_string()], apps)
-    }
-
-    /// Scan desktop files to find binaries that are GUI applications (Terminal=false)
-    fn get_known_gui_binaries() -> std::collections::HashSet<String> {
-        use std::collections::HashSet;
-        let mut gui_bins = HashSet::new();
-        let home = std::env::var("HOME").unwrap_or_else(|_| String::from("/home"));
-        let local_dir = format!("{}/.local/share/applications", home);
-        let paths = vec![local_dir, "/usr/share/applications".to_string()];
-
-        for dir in paths {
-            if let Ok(entries) = fs::read_dir(&dir) {
-                for entry in entries.flatten() {
-                    let path = entry.path();
-                    if path.extension().and_then(|s| s.to_str()) != Some("desktop") {
-                        continue;
-                    }
-                    if let Ok(content) = fs::read_to_string(&path) {
-                        let mut exec = None;
-                        let mut terminal = false;
-                        let mut in_desktop_entry = false;
-                        
-                        for line in content.lines() {
-                            let line = line.trim();
-                            if line.starts_with('[') {
-                                in_desktop_entry = line == "[Desktop Entry]";
-                                continue;
-                            }
-                            if !in_desktop_entry { continue; }
-                            
-                            if let Some((key, value)) = line.split_once('=') {
-                                let key = key.trim();
-                                let value = value.trim();
-                                match key {
-                                    "Exec" => exec = Some(value.to_string()),
-                                    "Terminal" => terminal = value == "true",
-                                    _ => {}
-                                }
-                            }
-                        }
-                        
-                        if let Some(exec_str) = exec {
-                            if !terminal {
-                                let clean = Self::clean_exec(&exec_str);
-                                if let Some(bin) = clean.split_whitespace().next() {
-                                    let bin_path = Path::new(bin);
-                                    if let Some(name) = bin_path.file_name().and_then(|s| s.to_str()) {
-                                        gui_bins.insert(name.to_string());
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
-            }
+    #[test]
+    fn test_dotted_names_activate_matches() {
+        let mut app = app_with("pass", &["github.com", "work/notes.txt"]);
+        for (query, name) in [("github.com", "github.com"), ("notes.txt", "work/notes.txt")] {
+            search(&mut app, query);
+            assert_eq!(app.selected_entry().map(|e| e.name.as_str()), Some(name));
         }
-        gui_bins
+
+        // A URL with a path is still offered first
+        search(&mut app, "github.com/bcorrigan/dstl");
+        assert_eq!(app.selected_entry().map(|e| &e.action), Some(&Action::Url("https://github.com/bcorrigan/dstl".to_string())));
     }
 }
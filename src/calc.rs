@@ -0,0 +1,348 @@
+//! Small arithmetic evaluator for the inline calculator row.
+//!
+//! Supports `+ - * / ^ %`, parentheses, unary minus, decimal/hex/octal/binary literals,
+//! constants (`pi`, `e`, `tau`, `phi`) and common functions (`sqrt`, `sin`, `log`, ...).
+
+use eyre::{Result, eyre};
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(f64),
+    Ident(String),
+    Op(char),
+    LParen,
+    RParen,
+    Comma,
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            c if c.is_whitespace() => i += 1,
+            '+' | '-' | '*' | '/' | '^' | '%' => {
+                tokens.push(Token::Op(c));
+                i += 1;
+            }
+            '(' => {
+                tokens.push(Token::LParen);
+                i += 1;
+            }
+            ')' => {
+                tokens.push(Token::RParen);
+                i += 1;
+            }
+            ',' => {
+                tokens.push(Token::Comma);
+                i += 1;
+            }
+            c if c.is_ascii_digit() || c == '.' => {
+                // Radix-prefixed integer literals: 0x1f, 0b101, 0o17
+                if c == '0' && i + 1 < chars.len() {
+                    let radix = match chars[i + 1].to_ascii_lowercase() {
+                        'x' => Some(16),
+                        'b' => Some(2),
+                        'o' => Some(8),
+                        _ => None,
+                    };
+                    if let Some(radix) = radix {
+                        let start = i + 2;
+                        let mut end = start;
+                        while end < chars.len() && (chars[end].is_digit(radix) || chars[end] == '_') {
+                            end += 1;
+                        }
+                        let digits: String = chars[start..end].iter().filter(|c| **c != '_').collect();
+                        let value = i64::from_str_radix(&digits, radix)
+                            .map_err(|_| eyre!("invalid base-{} literal", radix))?;
+                        tokens.push(Token::Number(value as f64));
+                        i = end;
+                        continue;
+                    }
+                }
+
+                let start = i;
+                while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.' || chars[i] == '_') {
+                    i += 1;
+                }
+                // Exponent: 1e3, 2.5E-4
+                if i < chars.len() && (chars[i] == 'e' || chars[i] == 'E') {
+                    let mut j = i + 1;
+                    if j < chars.len() && (chars[j] == '+' || chars[j] == '-') {
+                        j += 1;
+                    }
+                    if j < chars.len() && chars[j].is_ascii_digit() {
+                        i = j;
+                        while i < chars.len() && chars[i].is_ascii_digit() {
+                            i += 1;
+                        }
+                    }
+                }
+                let text: String = chars[start..i].iter().filter(|c| **c != '_').collect();
+                let value = text.parse::<f64>().map_err(|_| eyre!("invalid number '{}'", text))?;
+                tokens.push(Token::Number(value));
+            }
+            c if c.is_alphabetic() => {
+                let start = i;
+                while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                    i += 1;
+                }
+                tokens.push(Token::Ident(chars[start..i].iter().collect::<String>().to_lowercase()));
+            }
+            _ => return Err(eyre!("unexpected character '{}'", c)),
+        }
+    }
+
+    Ok(tokens)
+}
+
+/// Recursive descent over the token stream. Precedence, lowest first:
+/// `+ -`, `* / %`, unary `-`, `^` (right associative), then atoms.
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+    /// Operators and function calls seen; a lone literal is not treated as a calculation
+    operations: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn expect(&mut self, expected: Token) -> Result<()> {
+        match self.next() {
+            Some(t) if t == expected => Ok(()),
+            _ => Err(eyre!("expected {:?}", expected)),
+        }
+    }
+
+    fn expression(&mut self) -> Result<f64> {
+        let mut value = self.term()?;
+        while let Some(Token::Op(op @ ('+' | '-'))) = self.peek().cloned() {
+            self.pos += 1;
+            self.operations += 1;
+            let rhs = self.term()?;
+            value = if op == '+' { value + rhs } else { value - rhs };
+        }
+        Ok(value)
+    }
+
+    fn term(&mut self) -> Result<f64> {
+        let mut value = self.unary()?;
+        while let Some(Token::Op(op @ ('*' | '/' | '%'))) = self.peek().cloned() {
+            self.pos += 1;
+            self.operations += 1;
+            let rhs = self.unary()?;
+            value = match op {
+                '*' => value * rhs,
+                '/' => value / rhs,
+                _ => value % rhs,
+            };
+        }
+        Ok(value)
+    }
+
+    fn unary(&mut self) -> Result<f64> {
+        match self.peek() {
+            Some(Token::Op('-')) => {
+                self.pos += 1;
+                Ok(-self.unary()?)
+            }
+            Some(Token::Op('+')) => {
+                self.pos += 1;
+                self.unary()
+            }
+            _ => self.power(),
+        }
+    }
+
+    fn power(&mut self) -> Result<f64> {
+        let base = self.atom()?;
+        if let Some(Token::Op('^')) = self.peek() {
+            self.pos += 1;
+            self.operations += 1;
+            let exponent = self.unary()?;
+            return Ok(base.powf(exponent));
+        }
+        Ok(base)
+    }
+
+    fn atom(&mut self) -> Result<f64> {
+        match self.next() {
+            Some(Token::Number(n)) => Ok(n),
+            Some(Token::LParen) => {
+                let value = self.expression()?;
+                self.expect(Token::RParen)?;
+                Ok(value)
+            }
+            Some(Token::Ident(name)) => {
+                if let Some(Token::LParen) = self.peek() {
+                    self.pos += 1;
+                    let mut args = vec![self.expression()?];
+                    while let Some(Token::Comma) = self.peek() {
+                        self.pos += 1;
+                        args.push(self.expression()?);
+                    }
+                    self.expect(Token::RParen)?;
+                    self.operations += 1;
+                    call(&name, &args)
+                } else {
+                    constant(&name).ok_or_else(|| eyre!("unknown constant '{}'", name))
+                }
+            }
+            _ => Err(eyre!("unexpected end of expression")),
+        }
+    }
+}
+
+fn constant(name: &str) -> Option<f64> {
+    match name {
+        "pi" => Some(std::f64::consts::PI),
+        "e" => Some(std::f64::consts::E),
+        "tau" => Some(std::f64::consts::TAU),
+        "phi" => Some(1.618_033_988_749_895),
+        _ => None,
+    }
+}
+
+fn call(name: &str, args: &[f64]) -> Result<f64> {
+    let unary = |f: fn(f64) -> f64| -> Result<f64> {
+        match args {
+            [x] => Ok(f(*x)),
+            _ => Err(eyre!("{}() takes one argument", name)),
+        }
+    };
+    let binary = |f: fn(f64, f64) -> f64| -> Result<f64> {
+        match args {
+            [x, y] => Ok(f(*x, *y)),
+            _ => Err(eyre!("{}() takes two arguments", name)),
+        }
+    };
+
+    match name {
+        "sqrt" => unary(f64::sqrt),
+        "cbrt" => unary(f64::cbrt),
+        "abs" => unary(f64::abs),
+        "sin" => unary(f64::sin),
+        "cos" => unary(f64::cos),
+        "tan" => unary(f64::tan),
+        "asin" => unary(f64::asin),
+        "acos" => unary(f64::acos),
+        "atan" => unary(f64::atan),
+        "sinh" => unary(f64::sinh),
+        "cosh" => unary(f64::cosh),
+        "tanh" => unary(f64::tanh),
+        "ln" => unary(f64::ln),
+        "log" | "log10" => unary(f64::log10),
+        "log2" => unary(f64::log2),
+        "exp" => unary(f64::exp),
+        "floor" => unary(f64::floor),
+        "ceil" => unary(f64::ceil),
+        "round" => unary(f64::round),
+        "min" => binary(f64::min),
+        "max" => binary(f64::max),
+        "pow" => binary(f64::powf),
+        "atan2" => binary(f64::atan2),
+        _ => Err(eyre!("unknown function '{}'", name)),
+    }
+}
+
+/// Evaluate an arithmetic expression
+pub fn evaluate(input: &str) -> Result<f64> {
+    let mut parser = Parser { tokens: tokenize(input)?, pos: 0, operations: 0 };
+    let value = parser.expression()?;
+    if parser.pos != parser.tokens.len() {
+        return Err(eyre!("unexpected trailing input"));
+    }
+    Ok(value)
+}
+
+/// Evaluate `input` only if it looks like a calculation: it must parse and contain at least
+/// one operator or function call, so plain numbers and names are left to the app search
+pub fn evaluate_if_expression(input: &str) -> Option<f64> {
+    let mut parser = Parser { tokens: tokenize(input).ok()?, pos: 0, operations: 0 };
+    let value = parser.expression().ok()?;
+    (parser.pos == parser.tokens.len() && parser.operations > 0).then_some(value)
+}
+
+/// Format a result for display and output: integers without a fractional part, other
+/// values with up to 12 significant decimals
+pub fn format_result(value: f64) -> String {
+    if value.is_nan() {
+        return "NaN".to_string();
+    }
+    if value.is_infinite() {
+        return if value > 0.0 { "∞".to_string() } else { "-∞".to_string() };
+    }
+    if value.fract() == 0.0 && value.abs() < 1e15 {
+        return format!("{}", value as i64);
+    }
+
+    let formatted = format!("{:.12}", value);
+    formatted.trim_end_matches('0').trim_end_matches('.').to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn eval(s: &str) -> f64 {
+        evaluate(s).unwrap()
+    }
+
+    #[test]
+    fn test_precedence_and_associativity() {
+        assert_eq!(eval("2 + 3 * 4"), 14.0);
+        assert_eq!(eval("(2 + 3) * 4"), 20.0);
+        assert_eq!(eval("2 ^ 3 ^ 2"), 512.0);
+        assert_eq!(eval("-2 ^ 2"), -4.0);
+        assert_eq!(eval("10 % 4"), 2.0);
+        assert_eq!(eval("7 / 2"), 3.5);
+    }
+
+    #[test]
+    fn test_literals_constants_and_functions() {
+        assert_eq!(eval("0xff + 0b101 + 0o10"), 255.0 + 5.0 + 8.0);
+        assert_eq!(eval("1e3"), 1000.0);
+        assert_eq!(eval("sqrt(16)"), 4.0);
+        assert_eq!(eval("log(1000)"), 3.0);
+        assert_eq!(eval("max(2, 9)"), 9.0);
+        assert!((eval("sin(pi / 2)") - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_errors() {
+        assert!(evaluate("2 +").is_err());
+        assert!(evaluate("(1 + 2").is_err());
+        assert!(evaluate("foo(1)").is_err());
+        assert!(evaluate("firefox").is_err());
+    }
+
+    #[test]
+    fn test_only_calculations_are_detected() {
+        assert_eq!(evaluate_if_expression("2+2"), Some(4.0));
+        assert_eq!(evaluate_if_expression("sqrt(2)").map(format_result), Some("1.414213562373".to_string()));
+        assert_eq!(evaluate_if_expression("42"), None);
+        assert_eq!(evaluate_if_expression("pi"), None);
+        assert_eq!(evaluate_if_expression("7zip"), None);
+        assert_eq!(evaluate_if_expression("vim"), None);
+    }
+
+    #[test]
+    fn test_format_result() {
+        assert_eq!(format_result(4.0), "4");
+        assert_eq!(format_result(0.1 + 0.2), "0.3");
+        assert_eq!(format_result(-1.5), "-1.5");
+        assert_eq!(format_result(1.0 / 0.0), "∞");
+    }
+}
@@ -5,6 +5,7 @@ use ratatui::style::Color;
 use ratatui::widgets::BorderType;
 use rune_cfg::{RuneConfig, Value, RuneError};
 use serde::{Deserialize, Serialize};
+use crate::app::AppEntry;
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub enum SearchPosition {
@@ -25,6 +26,111 @@ pub enum CursorShape {
     Pipe,       // |
 }
 
+/// How text results (calculator, etc.) are handed back when selected
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum OutputMode {
+    Print,  // stdout
+    Copy,   // clipboard via OSC 52
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CalcConfig {
+    pub enabled: bool,
+    pub prefix: String,
+}
+
+/// A launcher item defined in the config rather than by a .desktop file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CustomEntry {
+    pub name: String,
+    pub exec: String,
+    pub category: String,
+    pub terminal: bool,
+    /// Glyph shown before the name, e.g. a nerd font icon
+    pub icon: Option<String>,
+}
+
+impl CustomEntry {
+    pub fn to_app_entry(&self) -> AppEntry {
+        AppEntry {
+            name: self.name.clone(),
+            category: self.category.clone(),
+            exec: self.exec.clone(),
+            terminal: self.terminal,
+            icon: self.icon.clone(),
+            source: "custom".to_string(),
+            ..Default::default()
+        }
+    }
+}
+
+/// How a script mode receives the chosen entry
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ScriptInput {
+    Argument, // first command line argument
+    Stdin,    // one line on stdin
+}
+
+/// An external entry provider, see `script.rs`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScriptConfig {
+    pub name: String,
+    pub exec: String,
+    pub input: ScriptInput,
+}
+
+/// Where the `files` source searches
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FilesConfig {
+    /// Directories to search; `~` stands for the home directory
+    pub roots: Vec<String>,
+    /// How many directories deep to descend below each root
+    pub max_depth: usize,
+    /// Also search hidden directories
+    pub hidden: bool,
+}
+
+/// Where the `projects` source looks for git repositories and what it runs for one
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectsConfig {
+    /// Directories to scan; `~` stands for the home directory
+    pub roots: Vec<String>,
+    /// How many directories deep to look below each root
+    pub max_depth: usize,
+    /// Command for a project, with `{path}` and `{terminal}` filled in
+    pub command: String,
+}
+
+/// A search engine reachable with a bang, e.g. `w tokio`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchEngine {
+    /// First word of the query that selects the engine
+    pub bang: String,
+    pub name: String,
+    /// Search URL with `{}` where the terms go
+    pub url: String,
+}
+
+/// The web row offered for URL-like queries and bang searches
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebConfig {
+    pub enabled: bool,
+    pub engines: Vec<SearchEngine>,
+}
+
+/// Commands for the `session` source. An empty `logout` exits sway over IPC.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionConfig {
+    pub lock: String,
+    pub logout: String,
+    pub suspend: String,
+    pub hibernate: String,
+    pub reboot: String,
+    pub poweroff: String,
+    /// Ask before logging out, rebooting or powering off
+    pub confirm: bool,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LauncherTheme {
     pub border: String,
@@ -51,6 +157,31 @@ pub struct DstlConfig {
     pub recent_first: bool,
     pub print_selection: bool,
     pub sway: bool,
+    pub smart_case: bool,
+    pub output_mode: OutputMode,
+    pub calc: CalcConfig,
+    pub entries: Vec<CustomEntry>,
+    pub scripts: Vec<ScriptConfig>,
+    /// Enabled sources by name, in the order the cycle key steps through them
+    pub sources: Vec<String>,
+    /// Sources merged by the `combi` source
+    pub combi: Vec<String>,
+    pub session: SessionConfig,
+    /// Show whatis descriptions in the man page source
+    pub man_descriptions: bool,
+    /// Command run for a password store entry, `{}` standing for its name; empty prints the name
+    pub pass_command: String,
+    /// Chromium-family `Bookmarks` files read by the bookmarks source
+    pub bookmark_files: Vec<String>,
+    /// Steam installations whose libraries the steam source reads
+    pub steam_roots: Vec<String>,
+    /// How the tmux source runs tmux, e.g. `tmux -L work` for another server
+    pub tmux_command: String,
+    /// Sway config read by the bindings source; empty searches where sway does
+    pub sway_config: String,
+    pub files: FilesConfig,
+    pub projects: ProjectsConfig,
+    pub web: WebConfig,
 }
 
 impl LauncherTheme {
@@ -59,9 +190,7 @@ impl LauncherTheme {
         let color = color.trim();
         
         // Handle hex colors (#RGB, #RRGGBB, #RRGGBBAA)
-        if color.starts_with('#') {
-            let hex = &color[1..];
-            
+        if let Some(hex) = color.strip_prefix('#') {
             match hex.len() {
                 // #RGB format
                 3 => {
@@ -147,6 +276,7 @@ fn extract_dstl_config(config: RuneConfig) -> Result<DstlConfig> {
     let recent_first = get_config_or(&config, "dstl.recent_first", false);
     let print_selection = get_config_or(&config, "dstl.print_selection", false);
     let sway = get_config_or(&config, "dstl.sway", false);
+    let smart_case = get_config_or(&config, "dstl.smart_case", false);
 
     // Validate search_position
     let search_position_str: String = get_config_or(&config, "dstl.search_position", "top".to_string());
@@ -164,6 +294,64 @@ fn extract_dstl_config(config: RuneConfig) -> Result<DstlConfig> {
         _ => StartMode::Single,
     };
 
+    let output_mode_str: String = get_config_or(&config, "dstl.output_mode", "print".to_string());
+    let output_mode = match output_mode_str.to_lowercase().as_str() {
+        "copy" => OutputMode::Copy,
+        _ => OutputMode::Print,
+    };
+
+    let calc = CalcConfig {
+        enabled: get_config_or(&config, "dstl.calc.enabled", true),
+        prefix: get_config_or(&config, "dstl.calc.prefix", "=".to_string()),
+    };
+
+    let entries = load_custom_entries(&config);
+    let scripts = load_scripts(&config);
+    let web = WebConfig {
+        enabled: get_config_or(&config, "dstl.web.enabled", true),
+        engines: load_search_engines(&config),
+    };
+
+    // Desktop apps, PATH binaries and every script unless chosen explicitly
+    let default_sources: Vec<String> = ["apps", "dmenu"].iter()
+        .map(|s| s.to_string())
+        .chain(scripts.iter().map(|s| s.name.clone()))
+        .collect();
+    let sources = get_config_or(&config, "dstl.sources", default_sources);
+    let default_combi: Vec<String> = ["apps", "dmenu", "windows"].map(String::from).to_vec();
+    let combi = get_config_or(&config, "dstl.combi", default_combi);
+
+    let session = SessionConfig {
+        lock: get_config_or(&config, "dstl.session.lock", "loginctl lock-session".to_string()),
+        logout: get_config_or(&config, "dstl.session.logout", String::new()),
+        suspend: get_config_or(&config, "dstl.session.suspend", "systemctl suspend".to_string()),
+        hibernate: get_config_or(&config, "dstl.session.hibernate", "systemctl hibernate".to_string()),
+        reboot: get_config_or(&config, "dstl.session.reboot", "systemctl reboot".to_string()),
+        poweroff: get_config_or(&config, "dstl.session.poweroff", "systemctl poweroff".to_string()),
+        confirm: get_config_or(&config, "dstl.session.confirm", true),
+    };
+
+    let man_descriptions = get_config_or(&config, "dstl.man.descriptions", false);
+    let pass_command = get_config_or(&config, "dstl.pass.command", String::new());
+    let bookmark_files = get_config_or(&config, "dstl.bookmarks.files", default_bookmark_files());
+    let steam_roots = get_config_or(&config, "dstl.steam.roots", default_steam_roots());
+    let tmux_command = get_config_or(&config, "dstl.tmux.command", "tmux".to_string());
+    let sway_config = get_config_or(&config, "dstl.bindings.config", String::new());
+    let files = FilesConfig {
+        roots: get_config_or(&config, "dstl.files.roots", vec!["~".to_string()]),
+        max_depth: get_config_or(&config, "dstl.files.max_depth", 8),
+        hidden: get_config_or(&config, "dstl.files.hidden", false),
+    };
+    let projects = ProjectsConfig {
+        roots: get_config_or(&config, "dstl.projects.roots", vec!["~".to_string()]),
+        max_depth: get_config_or(&config, "dstl.projects.max_depth", 3),
+        command: get_config_or(
+            &config,
+            "dstl.projects.command",
+            "{terminal} --working-directory {path}".to_string(),
+        ),
+    };
+
     // Load colors with theme priority system
     let (border_color, focus_color, unfocused_color, highlight_color, cursor_color) = load_theme_colors(&config)?;
 
@@ -204,9 +392,129 @@ fn extract_dstl_config(config: RuneConfig) -> Result<DstlConfig> {
         recent_first,
         print_selection,
         sway,
+        smart_case,
+        output_mode,
+        calc,
+        entries,
+        scripts,
+        sources,
+        combi,
+        session,
+        man_descriptions,
+        pass_command,
+        bookmark_files,
+        steam_roots,
+        tmux_command,
+        sway_config,
+        files,
+        projects,
+        web,
     })
 }
 
+/// Default profiles of Chromium, Chrome and Brave
+fn default_bookmark_files() -> Vec<String> {
+    [
+        "~/.config/chromium/Default/Bookmarks",
+        "~/.config/google-chrome/Default/Bookmarks",
+        "~/.config/BraveSoftware/Brave-Browser/Default/Bookmarks",
+    ]
+    .map(String::from)
+    .to_vec()
+}
+
+/// Native, legacy and Flatpak Steam installations
+fn default_steam_roots() -> Vec<String> {
+    [
+        "~/.local/share/Steam",
+        "~/.steam/steam",
+        "~/.var/app/com.valvesoftware.Steam/.local/share/Steam",
+    ]
+    .map(String::from)
+    .to_vec()
+}
+
+/// Load script modes from named blocks under `dstl.scripts`, in config order
+fn load_scripts(config: &RuneConfig) -> Vec<ScriptConfig> {
+    let keys = config.get_keys("dstl.scripts").unwrap_or_default();
+
+    keys.into_iter()
+        .filter_map(|key| {
+            let path = |field: &str| format!("dstl.scripts.{}.{}", key, field);
+            let exec: String = get_config_or(config, &path("exec"), String::new());
+            if exec.trim().is_empty() {
+                return None;
+            }
+
+            let input: String = get_config_or(config, &path("input"), "argument".to_string());
+            Some(ScriptConfig {
+                name: get_config_or(config, &path("name"), key.clone()),
+                exec,
+                input: match input.to_lowercase().as_str() {
+                    "stdin" => ScriptInput::Stdin,
+                    _ => ScriptInput::Argument,
+                },
+            })
+        })
+        .collect()
+}
+
+/// The engines in `dstl.web.engines`, each a block named after its bang, or Google, Wikipedia
+/// and GitHub if none are configured. Engines without a `url` are skipped.
+fn load_search_engines(config: &RuneConfig) -> Vec<SearchEngine> {
+    let keys = config.get_keys("dstl.web.engines").unwrap_or_default();
+    if keys.is_empty() {
+        return default_search_engines();
+    }
+
+    keys.into_iter()
+        .filter_map(|bang| {
+            let path = |field: &str| format!("dstl.web.engines.{}.{}", bang, field);
+            let url: String = get_config_or(config, &path("url"), String::new());
+            if url.trim().is_empty() {
+                return None;
+            }
+            Some(SearchEngine { name: get_config_or(config, &path("name"), bang.clone()), bang, url })
+        })
+        .collect()
+}
+
+fn default_search_engines() -> Vec<SearchEngine> {
+    [
+        ("g", "Google", "https://www.google.com/search?q={}"),
+        ("w", "Wikipedia", "https://en.wikipedia.org/wiki/Special:Search?search={}"),
+        ("gh", "GitHub", "https://github.com/search?q={}"),
+    ]
+    .into_iter()
+    .map(|(bang, name, url)| SearchEngine { bang: bang.to_string(), name: name.to_string(), url: url.to_string() })
+    .collect()
+}
+
+/// Load user-defined entries from named blocks under `dstl.entries`, in config order.
+/// The block name is used when an entry has no `name`; entries without `exec` are skipped.
+fn load_custom_entries(config: &RuneConfig) -> Vec<CustomEntry> {
+    let keys = config.get_keys("dstl.entries").unwrap_or_default();
+
+    keys.into_iter()
+        .filter_map(|key| {
+            let path = |field: &str| format!("dstl.entries.{}.{}", key, field);
+            let exec: String = get_config_or(config, &path("exec"), String::new());
+            if exec.trim().is_empty() {
+                return None;
+            }
+
+            let icon: String = get_config_or(config, &path("icon"), String::new());
+            Some(CustomEntry {
+                name: get_config_or(config, &path("name"), key.clone()),
+                exec,
+                category: get_config_or(config, &path("category"), "Custom".to_string()),
+                terminal: get_config_or(config, &path("terminal"), false),
+                icon: (!icon.is_empty()).then_some(icon),
+            })
+        })
+        .collect()
+}
+
 /// Load theme colors with priority system similar to claw
 fn load_theme_colors(config: &RuneConfig) -> Result<(String, String, String, String, String)> {
     let mut border = None;
@@ -281,3 +589,158 @@ pub fn load_launcher_config() -> DstlConfig {
         process::exit(1);
     })
 }
+
+/// Config with every option at its default, for tests
+#[cfg(test)]
+pub fn test_config() -> DstlConfig {
+    DstlConfig {
+        dmenu: false,
+        search_position: SearchPosition::Top,
+        start_mode: StartMode::Single,
+        focus_search_on_switch: true,
+        colors: LauncherTheme {
+            border: "".to_string(),
+            focus: "".to_string(),
+            unfocused: "".to_string(),
+            highlight: "".to_string(),
+            border_style: "".to_string(),
+            highlight_type: "".to_string(),
+            cursor_color: "".to_string(),
+            cursor_shape: CursorShape::Block,
+            cursor_blink_interval: 0,
+        },
+        terminal: "foot".to_string(),
+        timeout: 0,
+        max_recent_apps: 0,
+        recent_first: false,
+        print_selection: false,
+        sway: false,
+        smart_case: false,
+        output_mode: OutputMode::Print,
+        calc: CalcConfig { enabled: true, prefix: "=".to_string() },
+        entries: Vec::new(),
+        scripts: Vec::new(),
+        sources: vec!["apps".to_string()],
+        combi: vec!["apps".to_string()],
+        session: SessionConfig {
+            lock: "loginctl lock-session".to_string(),
+            logout: String::new(),
+            suspend: "systemctl suspend".to_string(),
+            hibernate: "systemctl hibernate".to_string(),
+            reboot: "systemctl reboot".to_string(),
+            poweroff: "systemctl poweroff".to_string(),
+            confirm: true,
+        },
+        man_descriptions: false,
+        pass_command: String::new(),
+        bookmark_files: default_bookmark_files(),
+        steam_roots: default_steam_roots(),
+        tmux_command: "tmux".to_string(),
+        sway_config: String::new(),
+        files: FilesConfig { roots: vec!["~".to_string()], max_depth: 8, hidden: false },
+        projects: ProjectsConfig {
+            roots: vec!["~".to_string()],
+            max_depth: 3,
+            command: "{terminal} --working-directory {path}".to_string(),
+        },
+        web: WebConfig { enabled: true, engines: default_search_engines() },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_custom_entries() {
+        let config = RuneConfig::from_str(r#"
+dstl:
+    entries:
+        weather:
+            exec = "curl wttr.in"
+            terminal = true
+        end
+        lock:
+            name = "Lock Screen"
+            exec = "swaylock"
+            category = "System"
+            icon = "L"
+        end
+        broken:
+            name = "No command"
+        end
+    end
+end
+"#).unwrap();
+
+        let entries = load_custom_entries(&config);
+        assert_eq!(entries.len(), 2);
+
+        assert_eq!(entries[0].name, "weather");
+        assert_eq!(entries[0].category, "Custom");
+        assert!(entries[0].terminal);
+        assert_eq!(entries[0].icon, None);
+
+        let lock = entries[1].to_app_entry();
+        assert_eq!(lock.name, "Lock Screen");
+        assert_eq!(lock.exec, "swaylock");
+        assert_eq!(lock.category, "System");
+        assert!(!lock.terminal);
+        assert_eq!(lock.icon.as_deref(), Some("L"));
+    }
+
+    #[test]
+    fn test_scripts() {
+        let config = RuneConfig::from_str(r#"
+dstl:
+    scripts:
+        bluetooth:
+            exec = "~/bin/bt-menu"
+        end
+        vpn:
+            name = "VPN"
+            exec = "vpn-menu"
+            input = "stdin"
+        end
+    end
+end
+"#).unwrap();
+
+        let scripts = load_scripts(&config);
+        assert_eq!(scripts.len(), 2);
+        assert_eq!(scripts[0].name, "bluetooth");
+        assert_eq!(scripts[0].input, ScriptInput::Argument);
+        assert_eq!(scripts[1].name, "VPN");
+        assert_eq!(scripts[1].input, ScriptInput::Stdin);
+    }
+
+    #[test]
+    fn test_search_engines() {
+        let config = RuneConfig::from_str(r#"
+dstl:
+    web:
+        engines:
+            ddg:
+                name = "DuckDuckGo"
+                url = "https://duckduckgo.com/?q={}"
+            end
+            crates:
+                url = "https://crates.io/search?q={}"
+            end
+            broken:
+                name = "No URL"
+            end
+        end
+    end
+end
+"#).unwrap();
+
+        let engines = load_search_engines(&config);
+        assert_eq!(engines.len(), 2);
+        assert_eq!((engines[0].bang.as_str(), engines[0].name.as_str()), ("ddg", "DuckDuckGo"));
+        assert_eq!(engines[1].name, "crates");
+
+        let defaults = load_search_engines(&RuneConfig::from_str("dstl:\n    dmenu = false\nend\n").unwrap());
+        assert_eq!(defaults.iter().map(|e| e.bang.as_str()).collect::<Vec<_>>(), ["g", "w", "gh"]);
+    }
+}
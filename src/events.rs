@@ -5,6 +5,21 @@ use tui_input::backend::crossterm::EventHandler;
 use tui_input::InputRequest;
 
 pub fn handle_key(app: &mut App, key: KeyEvent) -> Result<bool> {
+    // 0. A confirmation prompt takes every key until it is answered
+    if app.confirming.is_some() {
+        match key.code {
+            KeyCode::Char('c') if key.modifiers == KeyModifiers::CONTROL => return Ok(true),
+            KeyCode::Char('y') | KeyCode::Char('Y') => {
+                return Ok(app.answer_confirmation(true));
+            }
+            KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
+                app.answer_confirmation(false);
+            }
+            _ => {}
+        }
+        return Ok(false);
+    }
+
     // 1. Global / Exit keys
     match key.code {
         KeyCode::Esc => return Ok(true),
@@ -21,7 +36,7 @@ pub fn handle_key(app: &mut App, key: KeyEvent) -> Result<bool> {
             return Ok(false);
         }
         KeyCode::Char('x') if key.modifiers == KeyModifiers::CONTROL => {
-            app.toggle_dmenu_mode();
+            app.cycle_source();
             return Ok(false);
         }
         KeyCode::Tab => {
@@ -29,13 +44,14 @@ pub fn handle_key(app: &mut App, key: KeyEvent) -> Result<bool> {
             return Ok(false);
         }
         
+        // Alternate action (e.g. move window to workspace)
+        KeyCode::Enter if key.modifiers == KeyModifiers::ALT => {
+            return Ok(app.activate_selected(true));
+        }
+
         // Launch
         KeyCode::Enter => {
-            if let Some(app_entry) = get_selected_app(app) {
-                app.app_to_launch = Some(app_entry.exec.clone());
-                app.should_quit = true;
-                return Ok(true);
-            }
+            return Ok(app.activate_selected(false));
         }
         _ => {}
     }
@@ -130,12 +146,12 @@ fn navigate_up(app: &mut App) {
         Mode::DualPane => {
             match app.focus {
                 Focus::Categories => {
-                    let matching_categories = get_matching_category_indices(app);
-                    if let Some(current_pos) = matching_categories.iter().position(|&idx| idx == app.selected_category) {
-                        if current_pos > 0 {
-                            app.selected_category = matching_categories[current_pos - 1];
-                            app.selected_app = 0;
-                        }
+                    let matching_categories = app.matching_categories();
+                    if let Some(current_pos) = matching_categories.iter().position(|&idx| idx == app.selected_category)
+                        && current_pos > 0
+                    {
+                        app.selected_category = matching_categories[current_pos - 1];
+                        app.selected_app = 0;
                     }
                 }
                 _ => { // Focus::Apps or Search (effectively Apps)
@@ -151,7 +167,7 @@ fn navigate_up(app: &mut App) {
 fn navigate_down(app: &mut App) {
     match app.mode {
         Mode::SinglePane => {
-            let count = count_filtered_apps_in_current_category(app);
+            let count = app.row_count();
             if count > 0 && app.selected_app + 1 < count {
                 app.selected_app += 1;
             }
@@ -159,16 +175,16 @@ fn navigate_down(app: &mut App) {
         Mode::DualPane => {
             match app.focus {
                 Focus::Categories => {
-                    let matching_categories = get_matching_category_indices(app);
-                    if let Some(current_pos) = matching_categories.iter().position(|&idx| idx == app.selected_category) {
-                        if current_pos + 1 < matching_categories.len() {
-                            app.selected_category = matching_categories[current_pos + 1];
-                            app.selected_app = 0;
-                        }
+                    let matching_categories = app.matching_categories();
+                    if let Some(current_pos) = matching_categories.iter().position(|&idx| idx == app.selected_category)
+                        && current_pos + 1 < matching_categories.len()
+                    {
+                        app.selected_category = matching_categories[current_pos + 1];
+                        app.selected_app = 0;
                     }
                 }
                 _ => { // Focus::Apps
-                    let count = count_filtered_apps_in_current_category(app);
+                    let count = app.row_count();
                     if count > 0 && app.selected_app + 1 < count {
                         app.selected_app += 1;
                     }
@@ -196,35 +212,9 @@ fn navigate_right(app: &mut App) {
     }
 }
 
-fn get_matching_category_indices(app: &App) -> Vec<usize> {
-    let query = app.query();
-    if query.is_empty() {
-        (0..app.categories.len()).collect()
-    } else {
-        let query_lower = query.to_lowercase();
-        app.categories
-            .iter()
-            .enumerate()
-            .filter(|(_, cat_name)| {
-                if *cat_name == "Recent" {
-                    app.recent_apps.iter().any(|recent_name| {
-                        app.apps.iter()
-                            .find(|a| &a.name == recent_name)
-                            .and_then(|a| app.matches_search(&a.name, &query_lower))
-                            .is_some()
-                    })
-                } else {
-                    app.apps.iter().any(|a| {
-                        &a.category == *cat_name && app.matches_search(&a.name, &query_lower).is_some()
-                    })
-                }
-            })
-            .map(|(idx, _)| idx)
-            .collect()
-    }
-}
-
 fn update_selection_after_search(app: &mut App) {
+    app.refresh_pinned();
+
     if app.query().is_empty() {
         app.selected_category = 0;
         app.selected_app = 0;
@@ -233,7 +223,7 @@ fn update_selection_after_search(app: &mut App) {
 
     match app.mode {
         Mode::DualPane => {
-            let matching_indices = get_matching_category_indices(app);
+            let matching_indices = app.matching_categories();
             if let Some(&first_match) = matching_indices.first() {
                 app.selected_category = first_match;
                 app.selected_app = 0;
@@ -242,74 +232,3 @@ fn update_selection_after_search(app: &mut App) {
         Mode::SinglePane => { app.selected_app = 0; }
     }
 }
-
-fn get_selected_app(app: &App) -> Option<&crate::app::AppEntry> {
-    match app.mode {
-        Mode::SinglePane => {
-            app.visible_apps().get(app.selected_app).map(|v| &**v)
-        }
-        Mode::DualPane => {
-            let cat_name = app.categories.get(app.selected_category)?;
-            let query = app.query();
-            
-            if cat_name == "Recent" {
-                let apps_in_order: Vec<&crate::app::AppEntry> = app.recent_apps.iter()
-                    .filter_map(|recent_name| {
-                        app.apps.iter().find(|a| &a.name == recent_name)
-                    })
-                    .collect();
-                
-                if !query.is_empty() {
-                    let mut apps_with_scores: Vec<(&crate::app::AppEntry, i64)> = apps_in_order
-                        .into_iter()
-                        .filter_map(|a| app.matches_search(&a.name, &query).map(|score| (a, score)))
-                        .collect();
-                    apps_with_scores.sort_by(|a, b| b.1.cmp(&a.1));
-                    return apps_with_scores.get(app.selected_app).map(|(entry, _)| *entry);
-                }
-                
-                apps_in_order.get(app.selected_app).copied()
-            } else {
-                let mut apps_with_scores: Vec<(&crate::app::AppEntry, i64)> = app.apps.iter()
-                    .filter(|a| &a.category == cat_name)
-                    .filter_map(|a| app.matches_search(&a.name, &query).map(|score| (a, score)))
-                    .collect();
-
-                if !query.is_empty() {
-                    apps_with_scores.sort_by(|a, b| b.1.cmp(&a.1));
-                }
-
-                apps_with_scores.get(app.selected_app).map(|(entry, _)| *entry)
-            }
-        }
-    }
-}
-
-fn count_filtered_apps_in_current_category(app: &App) -> usize {
-    match app.mode {
-        Mode::SinglePane => {
-            app.visible_apps().len()
-        }
-        Mode::DualPane => {
-            let cat_name = match app.categories.get(app.selected_category) {
-                Some(c) => c,
-                None => return 0,
-            };
-            let query = app.query();
-            
-            if cat_name == "Recent" {
-                app.recent_apps.iter()
-                    .filter_map(|recent_name| {
-                        app.apps.iter().find(|a| &a.name == recent_name)
-                    })
-                    .filter(|a| app.matches_search(&a.name, &query).is_some())
-                    .count()
-            } else {
-                app.apps.iter()
-                    .filter(|a| &a.category == cat_name)
-                    .filter(|a| app.matches_search(&a.name, &query).is_some())
-                    .count()
-            }
-        }
-    }
-}
@@ -0,0 +1,92 @@
+//! Frecency: ranking by how often and how recently something was used, as in zoxide
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+use serde::{Deserialize, Serialize};
+
+const HOUR: u64 = 60 * 60;
+const DAY: u64 = 24 * HOUR;
+const WEEK: u64 = 7 * DAY;
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+struct Visit {
+    count: u32,
+    /// Seconds since the Unix epoch
+    last: u64,
+}
+
+/// Visit counts and times by key, stored as JSON in dstl's cache directory
+#[derive(Debug, Clone, Default)]
+pub struct Frecency {
+    path: Option<PathBuf>,
+    visits: HashMap<String, Visit>,
+}
+
+impl Frecency {
+    /// Load the store in `~/.cache/dstl/<file>`, empty if it doesn't exist yet
+    pub fn load(file: &str) -> Self {
+        let path = dirs::cache_dir().map(|dir| dir.join("dstl").join(file));
+        let visits = path.as_ref()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default();
+        Self { path, visits }
+    }
+
+    /// Count a visit to `key` now and save the store
+    pub fn record(&mut self, key: &str) -> std::io::Result<()> {
+        let visit = self.visits.entry(key.to_string()).or_default();
+        visit.count += 1;
+        visit.last = now();
+
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, serde_json::to_string(&self.visits)?)
+    }
+
+    /// Visits weighted by how long ago the last one was; 0 if never visited
+    pub fn score(&self, key: &str, now: u64) -> f64 {
+        let Some(visit) = self.visits.get(key) else {
+            return 0.0;
+        };
+        let age = now.saturating_sub(visit.last);
+        let weight = if age < HOUR {
+            4.0
+        } else if age < DAY {
+            2.0
+        } else if age < WEEK {
+            0.5
+        } else {
+            0.25
+        };
+        visit.count as f64 * weight
+    }
+}
+
+/// Seconds since the Unix epoch
+pub fn now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recent_visits_outrank_old_ones() {
+        let mut frecency = Frecency::default();
+        let now = 100 * WEEK;
+        frecency.visits.insert("often".to_string(), Visit { count: 6, last: now - 2 * WEEK });
+        frecency.visits.insert("today".to_string(), Visit { count: 1, last: now - 10 });
+
+        assert_eq!(frecency.score("often", now), 1.5);
+        assert_eq!(frecency.score("today", now), 4.0);
+        assert_eq!(frecency.score("never", now), 0.0);
+    }
+}
@@ -1,5 +1,9 @@
 use nerd_font_symbols::{fa, md, oct};
 
+pub const CALCULATOR: &str = fa::FA_CALCULATOR;
+pub const WEB: &str = fa::FA_GLOBE;
+pub const WEB_SEARCH: &str = fa::FA_MAGNIFYING_GLASS;
+
 pub fn category_icon(category: &str) -> &'static str {
     match category {
         "Recent" => fa::FA_CLOCK_ROTATE_LEFT,
@@ -13,6 +17,15 @@ pub fn category_icon(category: &str) -> &'static str {
         "Games" => fa::FA_GAMEPAD,
         "Education" => fa::FA_GRADUATION_CAP,
         "Settings" => fa::FA_SLIDERS,
+        "Custom" => fa::FA_STAR,
+        "SSH" => fa::FA_SERVER,
+        "Processes" => fa::FA_SKULL,
+        "Passwords" => fa::FA_KEY,
+        "Recent Files" => fa::FA_FILE,
+        "Files" => fa::FA_FOLDER,
+        "Projects" => fa::FA_CODE_BRANCH,
+        "Bookmarks" => fa::FA_BOOKMARK,
+        "tmux" => fa::FA_TERMINAL,
         _ => oct::OCT_DASH,
     }
 }
@@ -6,7 +6,7 @@ use crate::config::DstlConfig;
 pub fn build_command(entry: &AppEntry, config: &DstlConfig) -> Command {
     let terminal = &config.terminal;
 
-    if entry.terminal || entry.needs_terminal() {
+    if entry.terminal {
         // Terminal app
         let parts: Vec<&str> = terminal.split_whitespace().collect();
         if let Some((prog, args)) = parts.split_first() {
@@ -20,7 +20,8 @@ pub fn build_command(entry: &AppEntry, config: &DstlConfig) -> Command {
             if parts.len() == 1 {
                 c.arg("-e");
             }
-            c.arg(&entry.exec);
+            // Through a shell, so commands with arguments or `&&` work in every terminal
+            c.args(["sh", "-c", &entry.exec]);
             c
         } else {
              // Fallback for empty terminal config
@@ -36,6 +37,30 @@ pub fn build_command(entry: &AppEntry, config: &DstlConfig) -> Command {
     }
 }
 
+/// Fill in a command template: `{terminal}` becomes the configured terminal command and each
+/// `{name}` in `values` its shell-quoted value, e.g. `{terminal} --working-directory {path}`
+pub fn expand_template(template: &str, config: &DstlConfig, values: &[(&str, &str)]) -> String {
+    let mut command = template.replace("{terminal}", &config.terminal);
+    for (name, value) in values {
+        command = command.replace(&format!("{{{}}}", name), &shell_quote(value));
+    }
+    command
+}
+
+/// Quote an argument for `sh -c`, e.g. a file name substituted into a command template
+pub fn shell_quote(arg: &str) -> String {
+    format!("'{}'", arg.replace('\'', "'\\''"))
+}
+
+/// A command as a single shell command line with every part quoted, e.g. for sway's `exec`
+pub fn command_line(command: &Command) -> String {
+    std::iter::once(command.get_program())
+        .chain(command.get_args())
+        .map(|arg| shell_quote(&arg.to_string_lossy()))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
 pub fn launch_app(entry: &AppEntry, config: &DstlConfig) {
     let mut cmd = build_command(entry, config);
 
@@ -57,32 +82,10 @@ pub fn launch_app(entry: &AppEntry, config: &DstlConfig) {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::config::{DstlConfig, SearchPosition, StartMode, LauncherTheme, CursorShape};
+    use crate::config::DstlConfig;
 
     fn make_config(terminal: &str) -> DstlConfig {
-        DstlConfig {
-            dmenu: false,
-            search_position: SearchPosition::Top,
-            start_mode: StartMode::Single,
-            focus_search_on_switch: true,
-            colors: LauncherTheme {
-                border: "".to_string(),
-                focus: "".to_string(),
-                unfocused: "".to_string(),
-                highlight: "".to_string(),
-                border_style: "".to_string(),
-                highlight_type: "".to_string(),
-                cursor_color: "".to_string(),
-                cursor_shape: CursorShape::Block,
-                cursor_blink_interval: 0,
-            },
-            terminal: terminal.to_string(),
-            timeout: 0,
-            max_recent_apps: 0,
-            recent_first: false,
-            print_selection: false,
-            sway: false,
-        }
+        DstlConfig { terminal: terminal.to_string(), ..crate::config::test_config() }
     }
 
     #[test]
@@ -92,11 +95,12 @@ mod tests {
             category: "CLI".to_string(),
             exec: "vim".to_string(),
             terminal: true,
+            ..Default::default()
         };
         let config = make_config("alacritty");
         let cmd = build_command(&entry, &config);
         let debug_str = format!("{:?}", cmd);
-        // Expect: "alacritty" "-e" "vim"
+        // Expect: "alacritty" "-e" "sh" "-c" "vim"
         assert!(debug_str.contains("alacritty"));
         assert!(debug_str.contains("-e"));
         assert!(debug_str.contains("vim"));
@@ -109,11 +113,12 @@ mod tests {
             category: "CLI".to_string(),
             exec: "vim".to_string(),
             terminal: true,
+            ..Default::default()
         };
         let config = make_config("wezterm start");
         let cmd = build_command(&entry, &config);
         let debug_str = format!("{:?}", cmd);
-        // Expect: "wezterm" "start" "vim"
+        // Expect: "wezterm" "start" "sh" "-c" "vim"
         assert!(debug_str.contains("wezterm"));
         assert!(debug_str.contains("start"));
         assert!(debug_str.contains("vim"));
@@ -127,4 +132,62 @@ mod tests {
         
         // Actually, if I run `cargo test`, I'll see if it fails.
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_build_command_terminal_with_arguments() {
+        let entry = AppEntry {
+            name: "web".to_string(),
+            exec: "ssh -p 2222 web".to_string(),
+            terminal: true,
+            ..Default::default()
+        };
+        let cmd = build_command(&entry, &make_config("foot"));
+        let args: Vec<_> = cmd.get_args().collect();
+        assert_eq!(args, ["-e", "sh", "-c", "ssh -p 2222 web"]);
+    }
+
+    #[test]
+    fn test_expand_template() {
+        let config = make_config("foot");
+        let values = [("path", "/home/me/src/my repo")];
+        assert_eq!(
+            expand_template("{terminal} --working-directory {path}", &config, &values),
+            "foot --working-directory '/home/me/src/my repo'"
+        );
+        assert_eq!(expand_template("code {path}", &config, &values), "code '/home/me/src/my repo'");
+    }
+
+    #[test]
+    fn test_build_command_respects_terminal_flag() {
+        // Words like python or npm in a URL or path don't make it a terminal program
+        let entry = AppEntry {
+            name: "Search Google for python asyncio".to_string(),
+            exec: "xdg-open 'https://www.google.com/search?q=python%20asyncio'".to_string(),
+            ..Default::default()
+        };
+        let cmd = build_command(&entry, &make_config("foot"));
+        assert_eq!(cmd.get_program(), "sh");
+    }
+
+    #[test]
+    fn test_shell_quote() {
+        assert_eq!(shell_quote("work/mail"), "'work/mail'");
+        assert_eq!(shell_quote("it's"), "'it'\\''s'");
+    }
+
+    #[test]
+    fn test_command_line_keeps_arguments_literal() {
+        // As a file from the recent list or a walk might be named
+        let path = "/tmp/$(touch pwned) `id` \"q\" it's.txt";
+        let entry = AppEntry {
+            name: "file".to_string(),
+            exec: format!("printf %s {}", shell_quote(path)),
+            ..Default::default()
+        };
+        let line = command_line(&build_command(&entry, &make_config("foot")));
+
+        // sway hands exec commands to sh -c
+        let output = Command::new("sh").arg("-c").arg(&line).output().unwrap();
+        assert_eq!(String::from_utf8_lossy(&output.stdout), path);
+    }
+}
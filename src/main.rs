@@ -1,10 +1,16 @@
 mod app;
+mod calc;
 mod config;
 mod events;
+mod frecency;
 mod icons;
 mod launch;
+mod mime;
+mod search;
+mod sources;
 mod sway;
 mod ui;
+mod web;
 
 use crossterm::{
     ExecutableCommand,
@@ -27,8 +33,8 @@ use std::{
     time::{Duration, Instant},
 };
 
-use app::{App, Focus, Mode, SinglePaneMode};
-use config::{CursorShape, load_launcher_config};
+use app::{Action, App, AppEntry, Focus, Mode};
+use config::{CursorShape, OutputMode, load_launcher_config};
 
 fn main() -> Result<()> {
     color_eyre::install()?;
@@ -60,11 +66,9 @@ fn main() -> Result<()> {
                 Ok(mut stream) => {
                     let _ = stream.set_read_timeout(Some(Duration::from_millis(100)));
                     let mut buf = String::new();
-                    if let Ok(_) = stream.read_to_string(&mut buf) {
-                        if buf == "quit" {
-                            let _ = tx.send(());
-                            break;
-                        }
+                    if stream.read_to_string(&mut buf).is_ok() && buf == "quit" {
+                        let _ = tx.send(());
+                        break;
                     }
                 }
                 Err(_) => continue,
@@ -74,18 +78,17 @@ fn main() -> Result<()> {
 
     let cfg = load_launcher_config();
 
-    let single_pane_mode = if cfg.dmenu {
-        SinglePaneMode::Dmenu
-    } else {
-        SinglePaneMode::DesktopApps
-    };
+    let start_source = arg_value("--mode")
+        .unwrap_or_else(|| if cfg.dmenu { "dmenu" } else { "apps" }.to_string());
 
     let start_mode = match cfg.start_mode {
+        // Only desktop apps have a dual-pane view
+        _ if start_source != "apps" => Mode::SinglePane,
         config::StartMode::Dual => Mode::DualPane,
         config::StartMode::Single => Mode::SinglePane,
     };
 
-    let mut app = App::new(single_pane_mode, start_mode, &cfg);
+    let mut app = App::new(&start_source, start_mode, &cfg);
 
     let print_only = cfg.print_selection || std::env::args().any(|arg| arg == "--print-selection");
     let sway_mode = cfg.sway || std::env::args().any(|arg| arg == "--sway");
@@ -97,11 +100,11 @@ fn main() -> Result<()> {
     };
 
     let mut fullscreen_window_id = None;
-    if let Some(client) = &mut sway_client {
-        if let Ok(Some(id)) = client.get_focused_fullscreen_node_id() {
-            fullscreen_window_id = Some(id);
-            let _ = client.set_fullscreen(false, Some(id));
-        }
+    if let Some(client) = &mut sway_client
+        && let Ok(Some(id)) = client.get_focused_fullscreen_node_id()
+    {
+        fullscreen_window_id = Some(id);
+        let _ = client.set_fullscreen(false, Some(id));
     }
 
     enable_raw_mode()?;
@@ -121,67 +124,142 @@ fn main() -> Result<()> {
         eprintln!("Error: {err:?}");
     }
 
-    if let Some(ref cmd) = app.app_to_launch {
-        if print_only {
-            // Just print the command to stdout - useful for those who wish to pipe to swayexec or similar
-            // Check if app needs terminal
-            if let Some(entry) = app.apps.iter().find(|a| &a.exec == cmd) {
-                if entry.terminal || entry.needs_terminal() {
-                    println!("{} {}", app.config.terminal, cmd);
-                } else {
-                    println!("{}", cmd);
-                }
-            } else {
-                println!("{}", cmd);
+    let launched = match app.app_to_launch.clone() {
+        Some(entry) => match &entry.action {
+            Action::Launch => {
+                launch_entry(&mut app, &entry, print_only, sway_client.as_mut());
+                true
             }
-        } else {
-            // directly launch
-            if sway_mode {
-                let full_cmd = if let Some(entry) = app.apps.iter().find(|a| &a.exec == cmd).cloned() {
-                    app.add_to_recent(entry.name.clone());
-                    let command = crate::launch::build_command(&entry, &app.config);
-                    // Simple reconstruction of command string for sway exec
-                    let prog = command.get_program().to_string_lossy();
-                    let args = command.get_args()
-                        .map(|a| {
-                            let s = a.to_string_lossy();
-                            if s.contains(' ') {
-                                format!("\"{}\"", s)
-                            } else {
-                                s.into_owned()
-                            }
-                        })
-                        .collect::<Vec<_>>()
-                        .join(" ");
-                    format!("{} {}", prog, args)
-                } else {
-                    cmd.clone()
-                };
-
-                if let Some(client) = &mut sway_client {
-                    let _ = client.exec(&full_cmd);
+            Action::Terminal(command) => {
+                let entry = AppEntry { exec: command.clone(), terminal: true, ..entry.clone() };
+                launch_entry(&mut app, &entry, print_only, sway_client.as_mut());
+                true
+            }
+            Action::Url(url) => {
+                let opener = mime::open_command(Some("x-scheme-handler/https"), url);
+                let entry = AppEntry { exec: opener.exec, terminal: opener.terminal, ..entry.clone() };
+                launch_entry(&mut app, &entry, print_only, sway_client.as_mut());
+                true
+            }
+            Action::Output(text) => {
+                output_text(text, cfg.output_mode, print_only)?;
+                false
+            }
+            Action::Print(text) => {
+                println!("{}", text);
+                false
+            }
+            Action::Sway(command) => {
+                if print_only {
+                    println!("{}", command);
+                } else if let Some(client) = &mut sway_client {
+                    let _ = client.run_command(command);
+                } else if let Ok(mut client) = sway::Client::connect() {
+                    let _ = client.run_command(command);
                 }
-            } else {
-                if let Some(entry) = app.apps.iter().find(|a| &a.exec == cmd).cloned() {
-                    app.add_to_recent(entry.name.clone());
-                    crate::launch::launch_app(&entry, &app.config);
+                true
+            }
+            Action::Signal(pid, name, signal) => {
+                if print_only {
+                    println!("kill -{} {}", signal, pid);
                 } else {
-                    let _ = std::process::Command::new("sh").arg("-c").arg(cmd).spawn();
+                    sources::send_signal(*pid, name, *signal)?;
                 }
+                false
             }
+            // Handled inside the TUI; never set as the entry to launch
+            Action::Script(_) | Action::Submenu(_) => false,
+        },
+        None => false, // User cancelled
+    };
+
+    if !launched
+        && let Some(id) = fullscreen_window_id
+        && let Some(client) = &mut sway_client
+    {
+        let _ = client.set_fullscreen(true, Some(id));
+    }
+
+    Ok(())
+}
+
+/// Value of a `--flag value` or `--flag=value` command line argument
+fn arg_value(flag: &str) -> Option<String> {
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if arg == flag {
+            return args.next();
         }
+        if let Some(value) = arg.strip_prefix(flag).and_then(|rest| rest.strip_prefix('=')) {
+            return Some(value.to_string());
+        }
+    }
+    None
+}
+
+fn launch_entry(app: &mut App, entry: &AppEntry, print_only: bool, sway_client: Option<&mut sway::Client>) {
+    if print_only {
+        // Just print the command to stdout - useful for those who wish to pipe to swayexec or similar
+        if entry.terminal {
+            println!("{} {}", app.config.terminal, entry.exec);
+        } else {
+            println!("{}", entry.exec);
+        }
+        return;
+    }
+
+    app.record_launch(entry);
+
+    if let Some(client) = sway_client {
+        let command = crate::launch::build_command(entry, &app.config);
+        // sway runs exec through sh, so every part is quoted to keep file names and URLs literal
+        let _ = client.exec(&crate::launch::command_line(&command));
     } else {
-        // User cancelled
-        if let Some(id) = fullscreen_window_id {
-            if let Some(client) = &mut sway_client {
-                let _ = client.set_fullscreen(true, Some(id));
+        // directly launch
+        crate::launch::launch_app(entry, &app.config);
+    }
+}
+
+/// Hand a text result back: print it, or copy it to the clipboard
+fn output_text(text: &str, mode: OutputMode, print_only: bool) -> Result<()> {
+    match mode {
+        OutputMode::Print => println!("{}", text),
+        OutputMode::Copy => {
+            // The TUI was drawn on stderr in print-only mode, so that's where the terminal is
+            if print_only {
+                copy_to_clipboard(&mut io::stderr(), text)?;
+            } else {
+                copy_to_clipboard(&mut io::stdout(), text)?;
             }
         }
     }
+    Ok(())
+}
 
+/// Copy text to the system clipboard using OSC 52
+fn copy_to_clipboard<W: Write>(writer: &mut W, text: &str) -> Result<()> {
+    // OSC 52 ; c ; base64 ST - Set clipboard contents
+    write!(writer, "\x1b]52;c;{}\x07", base64_encode(text.as_bytes()))?;
+    writer.flush()?;
     Ok(())
 }
 
+fn base64_encode(data: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(data.len().div_ceil(3) * 4);
+
+    for chunk in data.chunks(3) {
+        let b = [chunk[0], *chunk.get(1).unwrap_or(&0), *chunk.get(2).unwrap_or(&0)];
+        let n = (b[0] as u32) << 16 | (b[1] as u32) << 8 | b[2] as u32;
+        out.push(ALPHABET[(n >> 18) as usize & 63] as char);
+        out.push(ALPHABET[(n >> 12) as usize & 63] as char);
+        out.push(if chunk.len() > 1 { ALPHABET[(n >> 6) as usize & 63] as char } else { '=' });
+        out.push(if chunk.len() > 2 { ALPHABET[n as usize & 63] as char } else { '=' });
+    }
+
+    out
+}
+
 fn run_with_writer<W: Write + ExecutableCommand>(
     mut writer: W,
     app: &mut App,
@@ -290,6 +368,7 @@ where
         }
 
         app.update_cursor_blink();
+        app.poll_stream();
 
         terminal.draw(|f| ui::draw(f, app, cfg.search_position.clone(), cfg))?;
 
@@ -330,12 +409,12 @@ where
             break;
         }
 
-        if event::poll(tick)? {
-            if let Event::Key(key) = event::read()? {
-                last_input = Instant::now();
-                if events::handle_key(app, key)? {
-                    break;
-                }
+        if event::poll(tick)?
+            && let Event::Key(key) = event::read()?
+        {
+            last_input = Instant::now();
+            if events::handle_key(app, key)? {
+                break;
             }
         }
     }
@@ -0,0 +1,189 @@
+//! Default applications from `mimeapps.list`, for opening files and URLs
+
+use std::fs;
+use std::path::PathBuf;
+use crate::launch::shell_quote;
+
+/// How to open something: a command for `sh -c` and whether it runs in a terminal
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Opener {
+    pub exec: String,
+    pub terminal: bool,
+}
+
+/// The command opening `target` (a path or URL) with the default application for `mime`,
+/// falling back to `xdg-open` when none is set or its desktop file can't be found
+pub fn open_command(mime: Option<&str>, target: &str) -> Opener {
+    mime.and_then(|mime| default_app(mime, target))
+        .unwrap_or_else(|| Opener {
+            exec: format!("xdg-open {}", shell_quote(target)),
+            terminal: false,
+        })
+}
+
+fn default_app(mime: &str, target: &str) -> Option<Opener> {
+    let desktop_id = mimeapps_files().iter()
+        .filter_map(|path| fs::read_to_string(path).ok())
+        .find_map(|content| parse_default(&content, mime))?;
+
+    let content = application_dirs().iter()
+        .find_map(|dir| fs::read_to_string(dir.join(&desktop_id)).ok())?;
+    let (exec, terminal) = parse_desktop_exec(&content)?;
+    Some(Opener { exec: expand_exec(&exec, target), terminal })
+}
+
+fn config_home() -> Option<PathBuf> {
+    std::env::var_os("XDG_CONFIG_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| dirs::home_dir().map(|h| h.join(".config")))
+}
+
+fn data_dirs() -> Vec<PathBuf> {
+    let home = std::env::var_os("XDG_DATA_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| dirs::home_dir().map(|h| h.join(".local/share")));
+    let system = std::env::var("XDG_DATA_DIRS")
+        .ok()
+        .filter(|dirs| !dirs.is_empty())
+        .unwrap_or_else(|| "/usr/local/share:/usr/share".to_string());
+
+    home.into_iter()
+        .chain(system.split(':').filter(|d| !d.is_empty()).map(PathBuf::from))
+        .collect()
+}
+
+fn application_dirs() -> Vec<PathBuf> {
+    data_dirs().into_iter().map(|dir| dir.join("applications")).collect()
+}
+
+/// `mimeapps.list` locations in lookup order, per the XDG MIME applications spec
+fn mimeapps_files() -> Vec<PathBuf> {
+    let desktops: Vec<String> = std::env::var("XDG_CURRENT_DESKTOP")
+        .unwrap_or_default()
+        .split(':')
+        .filter(|d| !d.is_empty())
+        .map(str::to_lowercase)
+        .collect();
+
+    let mut dirs: Vec<PathBuf> = config_home().into_iter().collect();
+    dirs.push(PathBuf::from("/etc/xdg"));
+    dirs.extend(application_dirs());
+
+    let mut files = Vec::new();
+    for dir in dirs {
+        for desktop in &desktops {
+            files.push(dir.join(format!("{}-mimeapps.list", desktop)));
+        }
+        files.push(dir.join("mimeapps.list"));
+    }
+    files
+}
+
+/// The first desktop file id listed for `mime` under `[Default Applications]`
+fn parse_default(content: &str, mime: &str) -> Option<String> {
+    let mut in_defaults = false;
+
+    for line in content.lines() {
+        let line = line.trim();
+        if line.starts_with('[') {
+            in_defaults = line == "[Default Applications]";
+            continue;
+        }
+        if !in_defaults {
+            continue;
+        }
+        if let Some((key, value)) = line.split_once('=')
+            && key.trim() == mime
+        {
+            return value.split(';').map(str::trim).find(|id| !id.is_empty()).map(str::to_string);
+        }
+    }
+
+    None
+}
+
+/// `Exec` and `Terminal` from the `[Desktop Entry]` section of a desktop file
+fn parse_desktop_exec(content: &str) -> Option<(String, bool)> {
+    let mut in_desktop_entry = false;
+    let mut exec = None;
+    let mut terminal = false;
+
+    for line in content.lines() {
+        let line = line.trim();
+        if line.starts_with('[') {
+            in_desktop_entry = line == "[Desktop Entry]";
+            continue;
+        }
+        if !in_desktop_entry {
+            continue;
+        }
+        match line.split_once('=') {
+            Some(("Exec", value)) => exec = Some(value.trim().to_string()),
+            Some(("Terminal", value)) => terminal = value.trim() == "true",
+            _ => {}
+        }
+    }
+
+    exec.map(|exec| (exec, terminal))
+}
+
+/// Substitute `target` for the file and URL field codes of a desktop `Exec` line, dropping
+/// the others. If there are none, the target is appended.
+fn expand_exec(exec: &str, target: &str) -> String {
+    let quoted = shell_quote(target);
+    let mut result = String::new();
+    let mut substituted = false;
+    let mut chars = exec.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            result.push(c);
+            continue;
+        }
+        match chars.next() {
+            // Only one target, so it's passed once even if there are several codes
+            Some('f' | 'F' | 'u' | 'U') if !substituted => {
+                result.push_str(&quoted);
+                substituted = true;
+            }
+            Some('%') => result.push('%'),
+            _ => {}
+        }
+    }
+
+    let result = result.trim().to_string();
+    if substituted {
+        result
+    } else {
+        format!("{} {}", result, quoted)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_default() {
+        let content = "\
+[Added Associations]
+text/plain=gedit.desktop;
+[Default Applications]
+text/plain=org.gnome.TextEditor.desktop;gedit.desktop
+x-scheme-handler/https = firefox.desktop
+";
+        assert_eq!(parse_default(content, "text/plain").as_deref(), Some("org.gnome.TextEditor.desktop"));
+        assert_eq!(parse_default(content, "x-scheme-handler/https").as_deref(), Some("firefox.desktop"));
+        assert_eq!(parse_default(content, "image/png"), None);
+    }
+
+    #[test]
+    fn test_expand_exec() {
+        assert_eq!(expand_exec("firefox %u", "https://example.org"), "firefox 'https://example.org'");
+        assert_eq!(expand_exec("gimp-2.10 %U %i", "/tmp/a b.png"), "gimp-2.10 '/tmp/a b.png'");
+        assert_eq!(expand_exec("mpv --", "/tmp/x.mkv"), "mpv -- '/tmp/x.mkv'");
+        assert_eq!(expand_exec("sh -c 'echo 100%%' %f", "x"), "sh -c 'echo 100%' 'x'");
+    }
+}
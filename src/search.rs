@@ -0,0 +1,352 @@
+use std::cell::RefCell;
+use std::cmp::Reverse;
+use std::rc::Rc;
+use fuzzy_matcher::FuzzyMatcher;
+use fuzzy_matcher::skim::SkimMatcherV2;
+use unicode_normalization::UnicodeNormalization;
+use unicode_normalization::char::is_combining_mark;
+
+/// A ranked hit: the position of an entry in the list it was indexed from, and its score
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Match {
+    pub index: usize,
+    pub score: i64,
+    /// Found by the typo-tolerant fallback rather than the fuzzy matcher
+    pub typo: bool,
+}
+
+/// Shortest query the typo fallback will try to correct
+const MIN_TYPO_QUERY_LEN: usize = 3;
+
+/// Pre-normalized search haystacks plus a cache of the last query's results.
+///
+/// Drawing happens every tick and navigation on every keypress, so scoring is done once per
+/// distinct query and everyone else reads the cached, index-based result list.
+pub struct SearchIndex {
+    /// Normalized and lowercased, for case-insensitive matching
+    folded: Vec<String>,
+    /// Normalized with case preserved, for smart-case queries containing uppercase
+    cased: Vec<String>,
+    /// Folded words of each name plus its keywords, for the typo fallback
+    terms: Vec<Vec<String>>,
+    /// The same terms with case preserved, for smart-case queries
+    cased_terms: Vec<Vec<String>>,
+    smart_case: bool,
+    matcher: SkimMatcherV2,
+    cache: RefCell<Option<(String, Rc<[Match]>)>>,
+}
+
+/// Decompose (NFKD) and strip combining marks, so "Café" and "cafe" compare equal
+pub fn normalize(s: &str) -> String {
+    s.nfkd().filter(|c| !is_combining_mark(*c)).collect()
+}
+
+/// A query normalized the same way as the haystacks it will be matched against
+struct Prepared {
+    text: String,
+    case_sensitive: bool,
+}
+
+impl Clone for SearchIndex {
+    fn clone(&self) -> Self {
+        Self {
+            folded: self.folded.clone(),
+            cased: self.cased.clone(),
+            terms: self.terms.clone(),
+            cased_terms: self.cased_terms.clone(),
+            smart_case: self.smart_case,
+            matcher: Self::matcher(),
+            cache: RefCell::new(self.cache.borrow().clone()),
+        }
+    }
+}
+
+impl std::fmt::Debug for SearchIndex {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SearchIndex")
+            .field("entries", &self.folded.len())
+            .field("smart_case", &self.smart_case)
+            .field("cached_query", &self.cache.borrow().as_ref().map(|(q, _)| q.clone()))
+            .finish()
+    }
+}
+
+impl SearchIndex {
+    /// Build an index over the given `(name, keywords)` pairs, in order.
+    ///
+    /// With `smart_case`, a query containing an uppercase letter matches case-sensitively.
+    pub fn new<'a, I>(entries: I, smart_case: bool) -> Self
+    where
+        I: IntoIterator<Item = (&'a str, &'a [String])>,
+    {
+        let mut index = Self {
+            folded: Vec::new(),
+            cased: Vec::new(),
+            terms: Vec::new(),
+            cased_terms: Vec::new(),
+            smart_case,
+            matcher: Self::matcher(),
+            cache: RefCell::new(None),
+        };
+        index.extend(entries);
+        index
+    }
+
+    /// Append more `(name, keywords)` pairs, e.g. results streamed in by a background search
+    pub fn extend<'a, I>(&mut self, entries: I)
+    where
+        I: IntoIterator<Item = (&'a str, &'a [String])>,
+    {
+        for (name, keywords) in entries {
+            let name = normalize(name);
+            let name_folded = name.to_lowercase();
+
+            let mut entry_terms: Vec<String> = name.split_whitespace()
+                .map(str::to_string)
+                .collect();
+            if entry_terms.len() > 1 {
+                entry_terms.push(name.clone());
+            }
+            entry_terms.extend(keywords.iter().map(|k| normalize(k)));
+
+            self.terms.push(entry_terms.iter().map(|t| t.to_lowercase()).collect());
+            self.cased_terms.push(entry_terms);
+            self.cased.push(name);
+            self.folded.push(name_folded);
+        }
+
+        *self.cache.get_mut() = None;
+    }
+
+    // Haystacks and queries are case-folded up front when needed, so the matcher never folds itself
+    fn matcher() -> SkimMatcherV2 {
+        SkimMatcherV2::default().respect_case()
+    }
+
+    fn prepare(&self, query: &str) -> Prepared {
+        let normalized = normalize(query);
+        let case_sensitive = self.smart_case && normalized.chars().any(char::is_uppercase);
+        Prepared {
+            text: if case_sensitive { normalized } else { normalized.to_lowercase() },
+            case_sensitive,
+        }
+    }
+
+    fn score(&self, index: usize, query: &Prepared) -> Option<i64> {
+        if query.text.is_empty() {
+            return Some(0); // Empty query matches everything
+        }
+
+        let haystacks = if query.case_sensitive { &self.cased } else { &self.folded };
+        let haystack = haystacks.get(index)?;
+
+        // Exact prefix match gets highest priority
+        if haystack.starts_with(&query.text) {
+            return Some(i64::MAX); // Push to top
+        }
+
+        // Fuzzy match otherwise
+        self.matcher.fuzzy_match(haystack, &query.text)
+    }
+
+    /// All entries matching `query`, best first. Ties keep index order.
+    ///
+    /// An empty query matches every entry with a score of 0. If the fuzzy matcher finds nothing,
+    /// entries within a small edit distance of the query are returned instead, marked as `typo`.
+    /// Results for the most recent query are cached, so calling this repeatedly with an unchanged
+    /// query is cheap.
+    pub fn search(&self, query: &str) -> Rc<[Match]> {
+        if let Some((cached_query, results)) = self.cache.borrow().as_ref()
+            && cached_query == query
+        {
+            return Rc::clone(results);
+        }
+
+        let prepared = self.prepare(query);
+        let mut results: Vec<Match> = (0..self.folded.len())
+            .filter_map(|index| {
+                self.score(index, &prepared).map(|score| Match { index, score, typo: false })
+            })
+            .collect();
+        results.sort_by_key(|m| Reverse(m.score));
+
+        if results.is_empty() {
+            results = self.typo_matches(&prepared);
+        }
+
+        let results: Rc<[Match]> = results.into();
+        *self.cache.borrow_mut() = Some((query.to_string(), Rc::clone(&results)));
+        results
+    }
+
+    /// Fallback pass for queries like "fierfox": entries whose name words or keywords are within
+    /// a few edits of the query, closest first
+    fn typo_matches(&self, query: &Prepared) -> Vec<Match> {
+        let terms = if query.case_sensitive { &self.cased_terms } else { &self.terms };
+        let query: Vec<char> = query.text.chars().collect();
+        if query.len() < MIN_TYPO_QUERY_LEN {
+            return Vec::new();
+        }
+
+        // Allow roughly one edit per four characters
+        let max_distance = query.len().div_ceil(4);
+
+        let mut results: Vec<Match> = terms.iter()
+            .enumerate()
+            .filter_map(|(index, terms)| {
+                terms.iter()
+                    .map(|term| {
+                        let term: Vec<char> = term.chars().collect();
+                        // Also compare against a prefix, so a half-typed word can still be corrected
+                        let prefix = &term[..term.len().min(query.len())];
+                        edit_distance(&query, &term).min(edit_distance(&query, prefix))
+                    })
+                    .min()
+                    .filter(|&distance| distance <= max_distance)
+                    .map(|distance| Match { index, score: -(distance as i64), typo: true })
+            })
+            .collect();
+        results.sort_by_key(|m| Reverse(m.score));
+        results
+    }
+}
+
+/// Damerau-Levenshtein distance (optimal string alignment variant): insertions, deletions,
+/// substitutions and transpositions of adjacent characters each cost one edit
+fn edit_distance(a: &[char], b: &[char]) -> usize {
+    let width = b.len() + 1;
+    let mut d = vec![0usize; (a.len() + 1) * width];
+
+    for i in 0..=a.len() {
+        d[i * width] = i;
+    }
+    for (j, cell) in d.iter_mut().enumerate().take(width) {
+        *cell = j;
+    }
+
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            let mut best = (d[(i - 1) * width + j] + 1)
+                .min(d[i * width + j - 1] + 1)
+                .min(d[(i - 1) * width + j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                best = best.min(d[(i - 2) * width + j - 2] + 1);
+            }
+            d[i * width + j] = best;
+        }
+    }
+
+    d[a.len() * width + b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn index_with(smart_case: bool) -> SearchIndex {
+        let keywords = [Vec::new(), Vec::new(), vec!["photoshop".to_string()], Vec::new(), Vec::new()];
+        let names = ["Firefox", "Files", "GIMP", "Thunderbird Mail", "Café Finder"];
+        SearchIndex::new(names.into_iter().zip(keywords.iter().map(|k| k.as_slice())), smart_case)
+    }
+
+    fn index() -> SearchIndex {
+        index_with(false)
+    }
+
+    fn smart_index() -> SearchIndex {
+        index_with(true)
+    }
+
+    fn indices(results: &[Match]) -> Vec<usize> {
+        results.iter().map(|m| m.index).collect()
+    }
+
+    #[test]
+    fn test_empty_query_matches_all_in_order() {
+        assert_eq!(indices(&index().search("")), vec![0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_prefix_match_ranks_first() {
+        let results = index().search("FI");
+        assert_eq!(results[0].score, i64::MAX);
+        assert!(results.iter().all(|m| m.index != 2));
+    }
+
+    #[test]
+    fn test_results_are_cached_per_query() {
+        let index = index();
+        let first = index.search("thu");
+        let second = index.search("thu");
+        assert!(Rc::ptr_eq(&first, &second));
+        let other = index.search("gim");
+        assert!(!Rc::ptr_eq(&first, &other));
+        assert_eq!(other[0].index, 2);
+    }
+
+    #[test]
+    fn test_extend_invalidates_cache() {
+        let mut index = index();
+        assert_eq!(indices(&index.search("rep")), Vec::<usize>::new());
+        index.extend([("report.pdf", [].as_slice())]);
+        assert_eq!(indices(&index.search("rep")), vec![5]);
+    }
+
+    #[test]
+    fn test_diacritics_are_ignored() {
+        assert_eq!(indices(&smart_index().search("cafe")), vec![4]);
+        assert_eq!(indices(&smart_index().search("CAFÉ")), Vec::<usize>::new());
+        assert_eq!(indices(&smart_index().search("Cafe")), vec![4]);
+    }
+
+    #[test]
+    fn test_smart_case() {
+        // Lowercase query is case-insensitive
+        assert_eq!(indices(&smart_index().search("gimp")), vec![2]);
+        // Any uppercase makes it case-sensitive
+        assert_eq!(indices(&smart_index().search("Gimp")), Vec::<usize>::new());
+        assert_eq!(indices(&smart_index().search("GIMP")), vec![2]);
+
+        let ignore_case = SearchIndex::new([("GIMP", &[][..])], false);
+        assert_eq!(indices(&ignore_case.search("Gimp")), vec![0]);
+    }
+
+    #[test]
+    fn test_typo_fallback() {
+        let index = index();
+
+        let results = index.search("fierfox");
+        assert_eq!(indices(&results), vec![0]);
+        assert!(results[0].typo);
+
+        assert_eq!(indices(&index.search("thunderbrid")), vec![3]);
+        // Keywords are considered too
+        assert_eq!(indices(&index.search("fotoshop")), vec![2]);
+        // Too far from anything
+        assert!(index.search("zzzzzz").is_empty());
+
+        // Smart case applies to corrections as well
+        assert_eq!(indices(&smart_index().search("Fierfox")), vec![0]);
+        assert!(smart_index().search("FIERFOX").is_empty());
+    }
+
+    #[test]
+    fn test_typo_fallback_only_without_fuzzy_hits() {
+        let results = index().search("fire");
+        assert!(!results.is_empty());
+        assert!(results.iter().all(|m| !m.typo));
+    }
+
+    #[test]
+    fn test_edit_distance() {
+        let d = |a: &str, b: &str| {
+            edit_distance(&a.chars().collect::<Vec<_>>(), &b.chars().collect::<Vec<_>>())
+        };
+        assert_eq!(d("firefox", "firefox"), 0);
+        assert_eq!(d("fierfox", "firefox"), 1); // transposition
+        assert_eq!(d("firfox", "firefox"), 1); // insertion
+        assert_eq!(d("kitten", "sitting"), 3);
+        assert_eq!(d("", "abc"), 3);
+    }
+}
@@ -0,0 +1,275 @@
+//! Key bindings from the sway config, as a cheat-sheet that can also run them
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use crate::app::{Action, AppEntry};
+use super::{Context, Listing, Source};
+
+/// Bindings from `bindsym` and `bindcode` in the sway config (`bindings`), grouped by mode.
+/// Enter runs the bound command, aimed at the window focused before dstl opened.
+pub struct Bindings;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Binding {
+    /// Binding mode, `default` outside any `mode` block
+    mode: String,
+    keys: String,
+    command: String,
+}
+
+impl Source for Bindings {
+    fn name(&self) -> &str {
+        "bindings"
+    }
+
+    fn uses_previous_window(&self) -> bool {
+        true
+    }
+
+    // Bindings stay in config order
+    fn load(&self, ctx: &Context) -> Listing {
+        let path = match ctx.config.sway_config.as_str() {
+            "" => find_config(),
+            path => Some(super::expand_home(path)),
+        };
+        let Some(path) = path else {
+            return (Vec::new(), Vec::new());
+        };
+
+        let mut parser = Parser::default();
+        parser.parse_file(&path);
+
+        let apps: Vec<AppEntry> = parser.bindings.into_iter()
+            .map(|b| {
+                // Criteria make commands like `kill` or `floating toggle` apply to that window
+                let command = match ctx.previous_window {
+                    Some(id) => format!("[con_id={}] {}", id, b.command),
+                    None => b.command.clone(),
+                };
+                AppEntry {
+                    name: format!("{} → {}", b.keys, b.command),
+                    category: b.mode,
+                    action: Action::Sway(command),
+                    ..Default::default()
+                }
+            })
+            .collect();
+
+        (super::categories_of(&apps), apps)
+    }
+}
+
+/// The first config in sway's own search order
+fn find_config() -> Option<PathBuf> {
+    let home = dirs::home_dir().map(|home| home.join(".sway/config"));
+    let xdg = dirs::config_dir().map(|config| config.join("sway/config"));
+    [home, xdg, Some(PathBuf::from("/etc/sway/config"))]
+        .into_iter()
+        .flatten()
+        .find(|path| path.is_file())
+}
+
+/// What a `{` opened
+enum Block {
+    Mode(String),
+    /// `bindsym {` or `bindcode {`: every line inside is a binding
+    Bind,
+    /// `input`, `bar`, `output` and the like, whose contents aren't bindings
+    Other,
+}
+
+/// Reads a config and the files it includes, in the order sway would
+#[derive(Default)]
+struct Parser {
+    /// Variables from `set`, longest name first so `$mod` doesn't replace part of `$modifier`
+    vars: Vec<(String, String)>,
+    bindings: Vec<Binding>,
+    /// Files read so far, so an include loop stops
+    visited: Vec<PathBuf>,
+}
+
+impl Parser {
+    fn parse_file(&mut self, path: &Path) {
+        let path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+        if self.visited.contains(&path) {
+            return;
+        }
+        let Ok(content) = fs::read_to_string(&path) else {
+            return;
+        };
+        self.visited.push(path.clone());
+        let dir = path.parent().unwrap_or(Path::new("/"));
+
+        let mut blocks: Vec<Block> = Vec::new();
+        for line in join_continuations(&content) {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if line == "}" {
+                blocks.pop();
+                continue;
+            }
+
+            if let Some(rest) = line.strip_prefix("set ") {
+                if let Some((name, value)) = rest.trim().split_once(char::is_whitespace) {
+                    let value = self.expand(value.trim());
+                    self.set(name, value);
+                }
+                continue;
+            }
+            let line = self.expand(line);
+            let mode = blocks.iter().rev()
+                .find_map(|b| match b {
+                    Block::Mode(name) => Some(name.as_str()),
+                    _ => None,
+                })
+                .unwrap_or("default")
+                .to_string();
+
+            if let Some(head) = line.strip_suffix('{') {
+                let mut words = head.split_whitespace();
+                blocks.push(match words.next() {
+                    Some("mode") => Block::Mode(unquote(words.filter(|w| !w.starts_with("--")).collect::<Vec<_>>().join(" "))),
+                    Some("bindsym" | "bindcode") => Block::Bind,
+                    _ => Block::Other,
+                });
+                continue;
+            }
+
+            match blocks.last() {
+                Some(Block::Bind) => self.bind(mode, &line),
+                Some(Block::Other) => {}
+                Some(Block::Mode(_)) | None => {
+                    let (keyword, rest) = line.split_once(char::is_whitespace).unwrap_or((&line, ""));
+                    match keyword {
+                        "bindsym" | "bindcode" => self.bind(mode, rest),
+                        "include" => {
+                            for include in resolve_include(dir, rest.trim()) {
+                                self.parse_file(&include);
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+            }
+        }
+    }
+
+    fn set(&mut self, name: &str, value: String) {
+        self.vars.retain(|(n, _)| n != name);
+        let at = self.vars.iter().position(|(n, _)| n.len() < name.len()).unwrap_or(self.vars.len());
+        self.vars.insert(at, (name.to_string(), value));
+    }
+
+    fn expand(&self, line: &str) -> String {
+        let mut line = line.to_string();
+        for (name, value) in &self.vars {
+            line = line.replace(name.as_str(), value);
+        }
+        line
+    }
+
+    /// Add a binding from `[--flags] <keys> <command>`
+    fn bind(&mut self, mode: String, rest: &str) {
+        let mut rest = rest.trim();
+        while rest.starts_with("--") {
+            rest = rest.split_once(char::is_whitespace).map_or("", |(_, r)| r.trim_start());
+        }
+        if let Some((keys, command)) = rest.split_once(char::is_whitespace) {
+            self.bindings.push(Binding { mode, keys: keys.to_string(), command: command.trim().to_string() });
+        }
+    }
+}
+
+/// Config lines, with those ending in `\` joined to the next
+fn join_continuations(content: &str) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut current = String::new();
+    for line in content.lines() {
+        match line.strip_suffix('\\') {
+            Some(start) => {
+                current.push_str(start.trim_end());
+                current.push(' ');
+            }
+            None => {
+                current.push_str(line.trim_start());
+                lines.push(std::mem::take(&mut current));
+            }
+        }
+    }
+    if !current.is_empty() {
+        lines.push(current);
+    }
+    lines
+}
+
+/// Files named by an include: relative to the including file, with `~` and wildcards in the
+/// last component, e.g. `/etc/sway/config.d/*`
+fn resolve_include(dir: &Path, pattern: &str) -> Vec<PathBuf> {
+    let path = dir.join(super::expand_home(&unquote(pattern.to_string())));
+    let name: Vec<char> = path.file_name().unwrap_or_default().to_string_lossy().chars().collect();
+    if !name.iter().any(|c| matches!(c, '*' | '?' | '[')) {
+        return vec![path];
+    }
+
+    let Some(parent) = path.parent() else {
+        return Vec::new();
+    };
+    let mut matches: Vec<PathBuf> = fs::read_dir(parent)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter(|entry| {
+            let file_name: Vec<char> = entry.file_name().to_string_lossy().chars().collect();
+            super::files::glob_match(&name, &file_name)
+        })
+        .map(|entry| entry.path())
+        .collect();
+    matches.sort();
+    matches
+}
+
+fn unquote(text: String) -> String {
+    match text.strip_prefix('"').and_then(|t| t.strip_suffix('"')) {
+        Some(inner) => inner.to_string(),
+        None => text,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::test_config;
+
+    #[test]
+    fn test_parse_config() {
+        let mut config = test_config();
+        config.sway_config = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/sway/config").to_string();
+        let ctx = Context { config: &config, previous_window: None };
+        let (categories, apps) = Bindings.load(&ctx);
+
+        assert_eq!(categories, ["default", "resize"]);
+        let names: Vec<&str> = apps.iter().map(|a| a.name.as_str()).collect();
+        assert_eq!(names, [
+            "Mod4+Return → exec foot",
+            "Mod4+Shift+q → kill",
+            "Mod4+h → focus left",
+            "Mod4+36 → exec foot --server",
+            "Mod4+d → exec wmenu-run",
+            "Mod4+f → fullscreen",
+            "Mod4+r → mode \"resize\"",
+            "h → resize shrink width 10px",
+            "Return → mode \"default\"",
+            "Print → exec grim",
+            "XF86AudioRaiseVolume → exec wpctl set-volume @DEFAULT_AUDIO_SINK@ 5%+",
+        ]);
+        assert_eq!(apps[7].category, "resize");
+        assert_eq!(apps[1].action, Action::Sway("kill".to_string()));
+
+        // Commands are aimed at the window dstl was opened from
+        let ctx = Context { config: &config, previous_window: Some(42) };
+        let (_, apps) = Bindings.load(&ctx);
+        assert_eq!(apps[1].action, Action::Sway("[con_id=42] kill".to_string()));
+    }
+}
@@ -0,0 +1,115 @@
+//! Browser bookmarks from Chromium-family `Bookmarks` JSON files
+
+use std::fs;
+use nerd_font_symbols::fa;
+use serde_json::Value;
+use crate::app::{Action, AppEntry};
+use super::{Context, Listing, Source};
+
+/// Bookmarks from the files in `bookmarks.files` (`bookmarks`), named after the folders they
+/// are in. Enter opens the URL in the default browser.
+pub struct Bookmarks;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Bookmark {
+    /// Folder path and title, e.g. `Rust / Crates / ratatui`
+    name: String,
+    url: String,
+}
+
+impl Source for Bookmarks {
+    fn name(&self) -> &str {
+        "bookmarks"
+    }
+
+    fn load(&self, ctx: &Context) -> Listing {
+        let mut bookmarks: Vec<Bookmark> = Vec::new();
+        for file in &ctx.config.bookmark_files {
+            let Ok(content) = fs::read_to_string(super::expand_home(file)) else {
+                continue;
+            };
+            // The same bookmark synced into several browsers is listed once
+            for bookmark in parse_bookmarks(&content) {
+                if !bookmarks.contains(&bookmark) {
+                    bookmarks.push(bookmark);
+                }
+            }
+        }
+
+        let apps = bookmarks.into_iter()
+            .map(|b| AppEntry {
+                name: b.name,
+                category: "Bookmarks".to_string(),
+                icon: Some(fa::FA_BOOKMARK.to_string()),
+                description: Some(b.url.clone()),
+                keywords: vec![b.url.clone()],
+                action: Action::Url(b.url),
+                ..Default::default()
+            })
+            .collect();
+
+        (vec!["Bookmarks".to_string()], apps)
+    }
+}
+
+/// Flatten the bookmark bar, other and mobile bookmark trees. The roots themselves aren't
+/// part of the names; bookmarklets (`javascript:` URLs) are skipped.
+fn parse_bookmarks(content: &str) -> Vec<Bookmark> {
+    let mut bookmarks = Vec::new();
+    let Ok(json) = serde_json::from_str::<Value>(content) else {
+        return bookmarks;
+    };
+
+    for root in ["bookmark_bar", "other", "synced"] {
+        if let Some(children) = json["roots"][root]["children"].as_array() {
+            for child in children {
+                collect(child, &[], &mut bookmarks);
+            }
+        }
+    }
+
+    bookmarks
+}
+
+fn collect(node: &Value, folders: &[&str], bookmarks: &mut Vec<Bookmark>) {
+    let name = node["name"].as_str().unwrap_or_default();
+
+    match node["type"].as_str() {
+        Some("folder") => {
+            let mut path = folders.to_vec();
+            path.push(name);
+            for child in node["children"].as_array().into_iter().flatten() {
+                collect(child, &path, bookmarks);
+            }
+        }
+        Some("url") => {
+            let Some(url) = node["url"].as_str().filter(|url| !url.starts_with("javascript:")) else {
+                return;
+            };
+            let title = if name.is_empty() { url } else { name };
+            let name = folders.iter().copied().chain([title]).collect::<Vec<_>>().join(" / ");
+            bookmarks.push(Bookmark { name, url: url.to_string() });
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_bookmarks() {
+        let bookmarks = parse_bookmarks(include_str!("../../tests/fixtures/chromium_bookmarks.json"));
+        let names: Vec<&str> = bookmarks.iter().map(|b| b.name.as_str()).collect();
+        assert_eq!(names, [
+            "Rust / The Rust Programming Language",
+            "Rust / Crates / ratatui",
+            "Mail",
+            "https://example.com/untitled",
+        ]);
+        assert_eq!(bookmarks[1].url, "https://ratatui.rs/");
+
+        assert!(parse_bookmarks("not json").is_empty());
+    }
+}
@@ -0,0 +1,119 @@
+//! Combi mode: several sources merged into one list, like rofi's combi
+
+use std::rc::Rc;
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+use crate::app::AppEntry;
+use super::{Context, Listing, Source};
+
+/// The sources named in `combi`, ranked together (`combi`). Entries are tagged with the
+/// source they came from, which gets the selection back and is shown as a badge.
+pub struct Combi {
+    pub sources: Vec<Rc<dyn Source>>,
+}
+
+impl Combi {
+    /// The member an entry came from. Custom entries are loaded by `apps` but keep their own
+    /// badge, so they are recorded in the recent list like the apps around them.
+    fn source_of(&self, entry: &AppEntry) -> Option<&Rc<dyn Source>> {
+        let name = if entry.source == "custom" { "apps" } else { entry.source.as_str() };
+        self.sources.iter().find(|s| s.name() == name)
+    }
+}
+
+impl Source for Combi {
+    fn name(&self) -> &str {
+        "combi"
+    }
+
+    fn load(&self, ctx: &Context) -> Listing {
+        let mut categories: Vec<String> = Vec::new();
+        let mut apps = Vec::new();
+
+        for source in &self.sources {
+            let (source_categories, source_apps) = source.load(ctx);
+            for category in source_categories {
+                if !categories.contains(&category) {
+                    categories.push(category);
+                }
+            }
+            apps.extend(source_apps.into_iter().map(|mut app| {
+                if app.source.is_empty() {
+                    app.source = source.name().to_string();
+                }
+                app
+            }));
+        }
+
+        (categories, apps)
+    }
+
+    fn uses_previous_window(&self) -> bool {
+        self.sources.iter().any(|s| s.uses_previous_window())
+    }
+
+    fn shows_badges(&self) -> bool {
+        true
+    }
+
+    fn records_recent(&self, entry: &AppEntry) -> bool {
+        self.source_of(entry).is_some_and(|source| source.records_recent(entry))
+    }
+
+    /// The first source that recognises a category prefix, like `3:` for man pages, gets it
+    fn split_query<'q>(&self, query: &'q str) -> (Option<&'q str>, &'q str) {
+        self.sources.iter()
+            .map(|source| source.split_query(query))
+            .find(|(category, _)| category.is_some())
+            .unwrap_or((None, query))
+    }
+
+    fn offers(&self, query: &str, apps: &[AppEntry], ctx: &Context) -> Vec<AppEntry> {
+        self.sources.iter()
+            .flat_map(|source| {
+                source.offers(query, apps, ctx).into_iter().map(|mut entry| {
+                    entry.source = source.name().to_string();
+                    entry
+                })
+            })
+            .collect()
+    }
+
+    fn stream(&self, ctx: &Context) -> Option<Receiver<Vec<AppEntry>>> {
+        let streams: Vec<(String, Receiver<Vec<AppEntry>>)> = self.sources.iter()
+            .filter_map(|source| Some((source.name().to_string(), source.stream(ctx)?)))
+            .collect();
+        if streams.is_empty() {
+            return None;
+        }
+
+        // Forward every source's batches into one channel, tagged like loaded entries
+        let (tx, rx) = mpsc::channel();
+        for (name, stream) in streams {
+            let tx = tx.clone();
+            thread::spawn(move || {
+                for mut batch in stream {
+                    for app in &mut batch {
+                        if app.source.is_empty() {
+                            app.source = name.clone();
+                        }
+                    }
+                    if tx.send(batch).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+        Some(rx)
+    }
+
+    fn select(&self, entry: &AppEntry, ctx: &Context) -> Option<Listing> {
+        // A source that takes over selection (a script) replaces the merged list with its own
+        let source = self.source_of(entry)?;
+        let (categories, mut apps) = source.select(entry, ctx)?;
+        for app in &mut apps {
+            app.source = source.name().to_string();
+        }
+        Some((categories, apps))
+    }
+}
@@ -0,0 +1,273 @@
+//! Applications from .desktop files, plus the custom entries from the config
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use crate::app::AppEntry;
+use super::{Context, Listing, Source};
+
+/// Installed desktop applications (`apps`)
+pub struct DesktopApps;
+
+impl Source for DesktopApps {
+    fn name(&self) -> &str {
+        "apps"
+    }
+
+    fn records_recent(&self, _entry: &AppEntry) -> bool {
+        true
+    }
+
+    fn load(&self, ctx: &Context) -> Listing {
+        let (mut categories, mut apps) = load_desktop_apps();
+        // Desktop files don't always say when a program needs a terminal
+        for app in &mut apps {
+            app.terminal |= app.needs_terminal();
+        }
+
+        for custom in &ctx.config.entries {
+            if !categories.contains(&custom.category) {
+                categories.push(custom.category.clone());
+            }
+            // Custom entries replace desktop apps of the same name
+            apps.retain(|a| a.name != custom.name);
+            apps.push(custom.to_app_entry());
+        }
+
+        apps.sort_by_cached_key(|a| a.name.to_lowercase());
+        (categories, apps)
+    }
+}
+
+/// Load .desktop apps from local and system directories
+fn load_desktop_apps() -> (Vec<String>, Vec<AppEntry>) {
+    let mut apps = Vec::new();
+    let mut category_map: HashMap<String, Vec<String>> = HashMap::new();
+    let mut seen_apps: HashSet<String> = HashSet::new();
+    let mut seen_files: HashSet<String> = HashSet::new(); // Track processed .desktop files
+
+    let home = std::env::var("HOME").unwrap_or_else(|_| String::from("/home"));
+    let local_dir = format!("{}/.local/share/applications", home);
+
+    let paths = vec![local_dir, "/usr/share/applications".to_string()];
+
+    // Get current desktop environment once
+    let current_desktops: Vec<String> = std::env::var("XDG_CURRENT_DESKTOP")
+        .or_else(|_| std::env::var("DESKTOP_SESSION"))
+        .unwrap_or_default()
+        .split(':')
+        .map(|s| s.trim().to_lowercase())
+        .collect();
+
+    for dir in paths {
+        if let Ok(entries) = fs::read_dir(&dir) {
+            for entry in entries.flatten() {
+                let path = entry.path();
+                if path.extension().and_then(|s| s.to_str()) != Some("desktop") {
+                    continue;
+                }
+
+                // Get the base filename to check for duplicates across directories
+                let filename = path.file_name()
+                    .and_then(|n| n.to_str())
+                    .unwrap_or("")
+                    .to_string();
+                
+                // Skip if we've already processed this .desktop file from another directory
+                if seen_files.contains(&filename) {
+                    continue;
+                }
+                seen_files.insert(filename.clone());
+
+                if let Ok(content) = fs::read_to_string(&path) {
+                    let mut name = None;
+                    let mut generic_name = None;
+                    let mut exec = None;
+                    let mut categories = None;
+                    let mut no_display = false;
+                    let mut terminal = false;
+                    let mut keywords = Vec::new();
+                    let mut only_show_in: Option<Vec<String>> = None;
+                    let mut not_show_in: Option<Vec<String>> = None;
+                    let mut in_desktop_entry = false;
+
+                    for line in content.lines() {
+                        let line = line.trim();
+                        
+                        // Track sections
+                        if line.starts_with('[') {
+                            in_desktop_entry = line == "[Desktop Entry]";
+                            continue;
+                        }
+                        
+                        // Only parse inside [Desktop Entry] section
+                        if !in_desktop_entry {
+                            continue;
+                        }
+                        
+                        // Parse key=value pairs
+                        if let Some((key, value)) = line.split_once('=') {
+                            // Skip localized entries like Name[af]=, Comment[de]=, etc.
+                            if key.contains('[') {
+                                continue;
+                            }
+                            
+                            let key = key.trim();
+                            let value = value.trim();
+                            
+                            match key {
+                                "Name" => name = Some(value.to_string()),
+                                "GenericName" => generic_name = Some(value.to_string()),
+                                "Exec" => exec = Some(value.to_string()),
+                                "Categories" => categories = Some(value.to_string()),
+                                "NoDisplay" => no_display = value == "true",
+                                "Hidden" => no_display = no_display || value == "true",
+                                "Terminal" => terminal = value == "true",
+                                "Keywords" => {
+                                    keywords = value.split(';')
+                                        .map(|s| s.trim())
+                                        .filter(|s| !s.is_empty())
+                                        .map(|s| s.to_string())
+                                        .collect();
+                                }
+                                "OnlyShowIn" => {
+                                    only_show_in = Some(
+                                        value.split(';')
+                                            .map(|s| s.trim())
+                                            .filter(|s| !s.is_empty())
+                                            .map(|s| s.to_string())
+                                            .collect()
+                                    );
+                                }
+                                "NotShowIn" => {
+                                    not_show_in = Some(
+                                        value.split(';')
+                                            .map(|s| s.trim())
+                                            .filter(|s| !s.is_empty())
+                                            .map(|s| s.to_string())
+                                            .collect()
+                                    );
+                                }
+                                _ => {}
+                            }
+                        }
+                    }
+
+                    // Skip apps marked as NoDisplay or Hidden
+                    if no_display {
+                        continue;
+                    }
+                    
+                    // Use Name, or fallback to GenericName
+                    let name = name.or(generic_name);
+
+                    // Check OnlyShowIn - skip if specified and current desktop not in list
+                    if let Some(desktops) = &only_show_in {
+                        let allowed = desktops.iter()
+                            .any(|d| current_desktops.contains(&d.to_lowercase()));
+                        
+                        if !allowed {
+                            continue;
+                        }
+                    }
+
+                    // Check NotShowIn - skip if current desktop is in list
+                    if let Some(desktops) = &not_show_in {
+                        let blocked = desktops.iter()
+                            .any(|d| current_desktops.contains(&d.to_lowercase()));
+                        if blocked {
+                            continue;
+                        }
+                    }
+
+                    if let (Some(name), Some(exec)) = (name, exec) {
+                        // Skip if we've already seen this app name
+                        if seen_apps.contains(&name) {
+                            continue;
+                        }
+                        seen_apps.insert(name.clone());
+                        
+                        // Determine grouped category             
+                        let cat_group = if let Some(cats) = categories {
+                            group_category(&cats, &name)
+                        } else {
+                            group_category("", &name)
+                        };
+
+                        // Clean up Exec field codes (%f, %F, %u, %U, etc.)
+                        let exec_clean = clean_exec(&exec);
+
+                        apps.push(AppEntry {
+                            name: name.clone(),
+                            category: cat_group.clone(),
+                            exec: exec_clean,
+                            terminal,
+                            keywords,
+                            ..Default::default()
+                        });
+
+                        category_map
+                            .entry(cat_group)
+                            .or_default()
+                            .push(name);
+                    }
+                }
+            }
+        }
+    }
+
+    // Build the list of grouped categories with Recent first
+    let mut categories = vec!["Recent".to_string()];
+    
+    let category_order = vec![
+        "Utilities", "Development", "Network", "Audio/Video", "Graphics",
+        "System", "Office", "Games", "Education", "Settings"
+    ];
+    categories.extend(
+        category_order
+            .into_iter()
+            .filter(|c| category_map.contains_key(*c))
+            .map(|s| s.to_string())
+    );
+
+    (categories, apps)
+}
+
+/// Clean desktop entry Exec field by removing field codes
+pub fn clean_exec(exec: &str) -> String {
+    // Remove field codes like %f, %F, %u, %U, %d, %D, %n, %N, %i, %c, %k, %v, %m
+    let mut result = exec.to_string();
+    let field_codes = ["%f", "%F", "%u", "%U", "%d", "%D", "%n", "%N", "%i", "%c", "%k", "%v", "%m"];
+    for code in &field_codes {
+        result = result.replace(code, "");
+    }
+    result.trim().to_string()
+}
+
+/// Map raw .desktop categories to simplified groupings 
+fn group_category(raw: &str, app_name: &str) -> String {
+    let raw = raw.to_lowercase();
+
+    // Special case for Claw
+    if app_name.to_lowercase() == "claw" {
+        return "Utilities".to_string();
+    }
+
+    if app_name.to_lowercase() == "rofi" {
+        return "Utilities".to_string();
+    }
+
+    // Prioritize "game" before "network"
+    if raw.contains("game") { "Games".to_string() }
+    else if raw.contains("utility") { "Utilities".to_string() }
+    else if raw.contains("development") { "Development".to_string() }
+    else if raw.contains("network") { "Network".to_string() }
+    else if raw.contains("audio") || raw.contains("video") { "Audio/Video".to_string() }
+    else if raw.contains("graphics") || raw.contains("2dgraphics") || raw.contains("3dgraphics") {
+        "Graphics".to_string()
+    }
+    else if raw.contains("system") { "System".to_string() }
+    else if raw.contains("office") { "Office".to_string() }
+    else if raw.contains("education") { "Education".to_string() }
+    else if raw.contains("settings") { "Settings".to_string() }
+    else { "Utilities".to_string() }
+}
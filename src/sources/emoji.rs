@@ -0,0 +1,97 @@
+//! Emoji and other Unicode characters, from the table in data/emoji.txt compiled in by build.rs
+
+use crate::app::{Action, AppEntry};
+use super::{Context, Listing, Source};
+
+struct Emoji {
+    character: &'static str,
+    /// CLDR short name, or the Unicode name for non-emoji characters
+    name: &'static str,
+    group: &'static str,
+    keywords: &'static [&'static str],
+    /// Skin-tone variants: (character, tones)
+    tones: &'static [(&'static str, &'static str)],
+}
+
+static EMOJI: &[Emoji] = include!(concat!(env!("OUT_DIR"), "/emoji.rs"));
+
+/// Emoji and characters by name and keyword (`emoji`). Enter outputs the character (see
+/// `output_mode`); the alternate action lists the skin-tone variants of emoji that have them.
+pub struct EmojiPicker;
+
+impl EmojiPicker {
+    fn entry(character: &str, name: String, group: &str) -> AppEntry {
+        AppEntry {
+            name,
+            category: group.to_string(),
+            icon: Some(character.to_string()),
+            action: Action::Output(character.to_string()),
+            ..Default::default()
+        }
+    }
+}
+
+impl Source for EmojiPicker {
+    fn name(&self) -> &str {
+        "emoji"
+    }
+
+    fn load(&self, _ctx: &Context) -> Listing {
+        let apps: Vec<AppEntry> = EMOJI.iter()
+            .map(|emoji| {
+                let has_tones = !emoji.tones.is_empty();
+                AppEntry {
+                    keywords: emoji.keywords.iter().map(|k| k.replace('_', " ")).collect(),
+                    description: has_tones.then(|| "Alt-Enter for skin tones".to_string()),
+                    alt_action: has_tones.then(|| Action::Submenu(emoji.character.to_string())),
+                    ..Self::entry(emoji.character, emoji.name.to_string(), emoji.group)
+                }
+            })
+            .collect();
+
+        (super::categories_of(&apps), apps)
+    }
+
+    fn select(&self, entry: &AppEntry, _ctx: &Context) -> Option<Listing> {
+        let Action::Submenu(character) = &entry.action else {
+            return None;
+        };
+        let emoji = EMOJI.iter().find(|e| e.character == character)?;
+
+        let apps: Vec<AppEntry> = std::iter::once(Self::entry(emoji.character, emoji.name.to_string(), emoji.group))
+            .chain(emoji.tones.iter().map(|(character, tones)| {
+                Self::entry(character, format!("{}: {}", emoji.name, tones), emoji.group)
+            }))
+            .collect();
+        Some((vec![emoji.group.to_string()], apps))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::test_config;
+
+    #[test]
+    fn test_skin_tone_variants() {
+        let config = test_config();
+        let ctx = Context { config: &config, previous_window: None };
+        let (categories, apps) = EmojiPicker.load(&ctx);
+        assert_eq!(categories.first().map(String::as_str), Some("Smileys & Emotion"));
+
+        let wave = apps.iter().find(|a| a.name == "waving hand").unwrap();
+        assert_eq!(wave.action, Action::Output("👋".to_string()));
+        assert!(wave.keywords.contains(&"wave".to_string()));
+
+        let mut alternate = wave.clone();
+        alternate.action = alternate.alt_action.take().unwrap();
+        let (_, variants) = EmojiPicker.select(&alternate, &ctx).unwrap();
+        assert_eq!(variants.len(), 6);
+        assert_eq!(variants[1].name, "waving hand: light skin tone");
+        assert_eq!(variants[1].action, Action::Output("👋🏻".to_string()));
+
+        // Choosing a character quits and outputs it
+        assert!(EmojiPicker.select(wave, &ctx).is_none());
+        assert!(apps.iter().any(|a| a.name == "rightwards arrow" && a.alt_action.is_none()));
+    }
+}
@@ -0,0 +1,325 @@
+//! Files under the configured roots, found on a background thread
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+use std::time::{Duration, Instant};
+use nerd_font_symbols::fa;
+use crate::app::{Action, AppEntry};
+use crate::config::FilesConfig;
+use crate::launch::shell_quote;
+use super::{Context, Listing, Source};
+
+/// Most entries sent in one batch
+const BATCH_SIZE: usize = 256;
+/// Longest a found file waits before its batch is sent anyway
+const BATCH_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Files under `files.roots` (`files`), listed as they are found. Enter opens the file with
+/// its default handler, the alternate action opens its directory in the terminal.
+pub struct Files;
+
+impl Source for Files {
+    fn name(&self) -> &str {
+        "files"
+    }
+
+    fn load(&self, _ctx: &Context) -> Listing {
+        (vec!["Files".to_string()], Vec::new())
+    }
+
+    fn stream(&self, ctx: &Context) -> Option<Receiver<Vec<AppEntry>>> {
+        let config = ctx.config.files.clone();
+        let (tx, rx) = mpsc::channel();
+
+        thread::spawn(move || {
+            let mut walker = Walker::new(tx, config.clone(), dirs::home_dir());
+            for root in &config.roots {
+                if !walker.walk(&super::expand_home(root), 0, &mut Vec::new()) {
+                    return;
+                }
+            }
+            walker.flush();
+        });
+
+        Some(rx)
+    }
+}
+
+/// Depth-first search that batches up the files it finds and sends them on
+struct Walker {
+    tx: Sender<Vec<AppEntry>>,
+    config: FilesConfig,
+    /// Shown as `~` in paths
+    home: Option<PathBuf>,
+    batch: Vec<AppEntry>,
+    last_sent: Instant,
+}
+
+impl Walker {
+    fn new(tx: Sender<Vec<AppEntry>>, config: FilesConfig, home: Option<PathBuf>) -> Self {
+        Self { tx, config, home, batch: Vec::new(), last_sent: Instant::now() }
+    }
+
+    /// Search `dir`, with the ignore files of its ancestors in `ignores`. Returns false once
+    /// nobody is listening any more, to stop the whole search.
+    fn walk(&mut self, dir: &Path, depth: usize, ignores: &mut Vec<IgnoreFile>) -> bool {
+        let Ok(entries) = fs::read_dir(dir) else {
+            return true;
+        };
+
+        // .ignore comes last so it overrides .gitignore, as in ripgrep
+        let inherited = ignores.len();
+        for name in [".gitignore", ".ignore"] {
+            if let Ok(content) = fs::read_to_string(dir.join(name)) {
+                ignores.push(IgnoreFile { base: dir.to_path_buf(), rules: parse_ignore(&content) });
+            }
+        }
+
+        let mut entries: Vec<_> = entries.flatten().collect();
+        entries.sort_by_key(|e| e.file_name());
+
+        let mut listening = true;
+        for entry in entries {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if name == ".git" || (name.starts_with('.') && !self.config.hidden) {
+                continue;
+            }
+            // Symlinked directories aren't followed, which also rules out cycles
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            let path = entry.path();
+            if is_ignored(ignores, &path, file_type.is_dir()) {
+                continue;
+            }
+
+            listening = if file_type.is_dir() {
+                depth >= self.config.max_depth || self.walk(&path, depth + 1, ignores)
+            } else {
+                self.add(&path)
+            };
+            if !listening {
+                break;
+            }
+        }
+
+        ignores.truncate(inherited);
+        listening
+    }
+
+    fn add(&mut self, path: &Path) -> bool {
+        self.batch.push(self.to_app_entry(path));
+        if self.batch.len() >= BATCH_SIZE || self.last_sent.elapsed() >= BATCH_INTERVAL {
+            return self.flush();
+        }
+        true
+    }
+
+    fn flush(&mut self) -> bool {
+        self.last_sent = Instant::now();
+        self.batch.is_empty() || self.tx.send(std::mem::take(&mut self.batch)).is_ok()
+    }
+
+    fn to_app_entry(&self, path: &Path) -> AppEntry {
+        let target = path.to_string_lossy();
+        let dir = path.parent().map(|p| p.to_string_lossy().into_owned()).unwrap_or_default();
+        let opener = crate::mime::open_command(None, &target);
+
+        AppEntry {
+            name: super::tilde(path, self.home.as_deref()),
+            category: "Files".to_string(),
+            exec: opener.exec,
+            terminal: opener.terminal,
+            icon: Some(fa::FA_FILE.to_string()),
+            alt_action: Some(Action::Terminal(format!("cd {} && exec \"${{SHELL:-sh}}\"", shell_quote(&dir)))),
+            ..Default::default()
+        }
+    }
+}
+
+/// One line of a `.gitignore` or `.ignore` file
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct IgnoreRule {
+    pattern: Vec<char>,
+    /// `!pattern` re-includes what an earlier rule excluded
+    negated: bool,
+    /// `pattern/` only matches directories
+    dir_only: bool,
+    /// A pattern containing `/` matches the path relative to the ignore file, otherwise it
+    /// matches the name at any depth
+    anchored: bool,
+}
+
+struct IgnoreFile {
+    /// Directory the ignore file is in
+    base: PathBuf,
+    rules: Vec<IgnoreRule>,
+}
+
+fn parse_ignore(content: &str) -> Vec<IgnoreRule> {
+    content.lines()
+        .filter_map(|line| {
+            let line = line.trim_end();
+            if line.is_empty() || line.starts_with('#') {
+                return None;
+            }
+            let (negated, line) = match line.strip_prefix('!') {
+                Some(rest) => (true, rest),
+                None => (false, line),
+            };
+            // `\#` and `\!` escape a leading special character
+            let line = line.strip_prefix('\\').filter(|l| l.starts_with(['#', '!'])).unwrap_or(line);
+            let (dir_only, line) = match line.strip_suffix('/') {
+                Some(rest) => (true, rest),
+                None => (false, line),
+            };
+            let anchored = line.contains('/');
+            let pattern = line.strip_prefix('/').unwrap_or(line);
+            (!pattern.is_empty()).then(|| IgnoreRule { pattern: pattern.chars().collect(), negated, dir_only, anchored })
+        })
+        .collect()
+}
+
+/// Whether `path` is excluded by the ignore files that apply to it. Later rules win, and
+/// deeper files come later.
+fn is_ignored(ignores: &[IgnoreFile], path: &Path, is_dir: bool) -> bool {
+    let name: Vec<char> = path.file_name().unwrap_or_default().to_string_lossy().chars().collect();
+    let mut ignored = false;
+
+    for file in ignores {
+        let Ok(relative) = path.strip_prefix(&file.base) else {
+            continue;
+        };
+        let relative: Vec<char> = relative.to_string_lossy().chars().collect();
+        for rule in &file.rules {
+            if rule.dir_only && !is_dir {
+                continue;
+            }
+            let text = if rule.anchored { &relative } else { &name };
+            if glob_match(&rule.pattern, text) {
+                ignored = !rule.negated;
+            }
+        }
+    }
+
+    ignored
+}
+
+/// Gitignore-style glob: `*` and `?` stay within a path component, `**` crosses them,
+/// `[a-z]` and `[!a-z]` match character classes
+pub(super) fn glob_match(pattern: &[char], text: &[char]) -> bool {
+    match pattern {
+        [] => text.is_empty(),
+        ['*', '*', '/', rest @ ..] => {
+            // Zero or more whole directories
+            glob_match(rest, text)
+                || text.iter().enumerate().any(|(i, &c)| c == '/' && glob_match(rest, &text[i + 1..]))
+        }
+        ['*', '*', rest @ ..] => (0..=text.len()).any(|i| glob_match(rest, &text[i..])),
+        ['*', rest @ ..] => {
+            for i in 0..=text.len() {
+                if glob_match(rest, &text[i..]) {
+                    return true;
+                }
+                if text.get(i) == Some(&'/') {
+                    break;
+                }
+            }
+            false
+        }
+        ['?', rest @ ..] => matches!(text.first(), Some(&c) if c != '/') && glob_match(rest, &text[1..]),
+        ['[', rest @ ..] if rest.contains(&']') => {
+            let Some(&c) = text.first() else {
+                return false;
+            };
+            let (negated, class) = match rest {
+                ['!' | '^', class @ ..] => (true, class),
+                class => (false, class),
+            };
+            // A `]` right after the opening bracket is part of the class
+            let end = class.iter().skip(1).position(|&c| c == ']').map_or(0, |i| i + 1);
+            if class.get(end) != Some(&']') {
+                return c == '[' && glob_match(rest, &text[1..]);
+            }
+            let members = &class[..end];
+            let mut found = false;
+            let mut i = 0;
+            while i < members.len() {
+                if i + 2 < members.len() && members[i + 1] == '-' {
+                    found |= (members[i]..=members[i + 2]).contains(&c);
+                    i += 3;
+                } else {
+                    found |= members[i] == c;
+                    i += 1;
+                }
+            }
+            found != negated && c != '/' && glob_match(&class[end + 1..], &text[1..])
+        }
+        ['\\', c, rest @ ..] => text.first() == Some(c) && glob_match(rest, &text[1..]),
+        [c, rest @ ..] => text.first() == Some(c) && glob_match(rest, &text[1..]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn glob(pattern: &str, text: &str) -> bool {
+        glob_match(&pattern.chars().collect::<Vec<_>>(), &text.chars().collect::<Vec<_>>())
+    }
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob("*.log", "debug.log"));
+        assert!(!glob("*.log", "logs/debug.log"));
+        assert!(glob("**/build", "build"));
+        assert!(glob("**/build", "a/b/build"));
+        assert!(glob("docs/**", "docs/a/b.md"));
+        assert!(glob("a/**/b", "a/x/y/b"));
+        assert!(glob("file[0-9].txt", "file3.txt"));
+        assert!(!glob("file[!0-9].txt", "file3.txt"));
+        assert!(glob("\\#notes", "#notes"));
+    }
+
+    #[test]
+    fn test_ignore_rules() {
+        let rules = parse_ignore("# comment\n*.log\n!keep.log\ntarget/\n/secret.txt\ndocs/*.pdf\n");
+        let ignores = [IgnoreFile { base: PathBuf::from("/repo"), rules }];
+        let ignored = |path: &str, is_dir| is_ignored(&ignores, Path::new(path), is_dir);
+
+        assert!(ignored("/repo/src/debug.log", false));
+        assert!(!ignored("/repo/keep.log", false));
+        assert!(ignored("/repo/sub/target", true));
+        assert!(!ignored("/repo/target", false));
+        assert!(ignored("/repo/secret.txt", false));
+        assert!(!ignored("/repo/sub/secret.txt", false));
+        assert!(ignored("/repo/docs/manual.pdf", false));
+        assert!(!ignored("/repo/other/docs/manual.pdf", false));
+    }
+
+    #[test]
+    fn test_walk() {
+        let root = std::env::temp_dir().join(format!("dstl-files-test-{}", std::process::id()));
+        for dir in ["src", "target/debug", ".cache", "a/b/c"] {
+            fs::create_dir_all(root.join(dir)).unwrap();
+        }
+        for file in ["README.md", "src/main.rs", "src/notes.tmp", "target/debug/dstl", ".cache/x", "a/b/c/deep.txt"] {
+            fs::write(root.join(file), "").unwrap();
+        }
+        fs::write(root.join(".gitignore"), "target/\n").unwrap();
+        fs::write(root.join("src/.ignore"), "*.tmp\n").unwrap();
+
+        let (tx, rx) = mpsc::channel();
+        let config = FilesConfig { roots: Vec::new(), max_depth: 1, hidden: false };
+        let mut walker = Walker::new(tx, config, Some(root.clone()));
+        assert!(walker.walk(&root, 0, &mut Vec::new()));
+        walker.flush();
+        drop(walker);
+        fs::remove_dir_all(&root).unwrap();
+
+        let names: Vec<String> = rx.iter().flatten().map(|a| a.name).collect();
+        assert_eq!(names, ["~/README.md", "~/src/main.rs"]);
+    }
+}
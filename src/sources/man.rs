@@ -0,0 +1,192 @@
+//! Man pages from the manpath directories, by section and name
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use crate::app::AppEntry;
+use crate::launch::shell_quote;
+use super::{Context, Listing, Source};
+
+/// Compression suffixes man pages are commonly installed with
+const COMPRESSED: [&str; 5] = [".gz", ".bz2", ".xz", ".zst", ".lzma"];
+
+/// Installed man pages (`man`), categorised by section. Enter opens `man <section> <name>` in
+/// the terminal; a query like `3:printf` only searches section 3.
+pub struct ManPages;
+
+impl Source for ManPages {
+    fn name(&self) -> &str {
+        "man"
+    }
+
+    fn load(&self, ctx: &Context) -> Listing {
+        let mut pages: Vec<(String, String)> = Vec::new();
+        let mut seen = HashSet::new();
+        for dir in manpath() {
+            for page in index_dir(Path::new(&dir)) {
+                if seen.insert(page.clone()) {
+                    pages.push(page);
+                }
+            }
+        }
+
+        let descriptions: HashMap<(String, String), String> = if ctx.config.man_descriptions {
+            whatis().into_iter().map(|(name, section, description)| ((name, section), description)).collect()
+        } else {
+            HashMap::new()
+        };
+
+        let mut apps: Vec<AppEntry> = pages.into_iter()
+            .map(|(name, section)| {
+                let description = descriptions.get(&(name.clone(), section.clone())).cloned();
+                page_entry(&name, section, description)
+            })
+            .collect();
+        apps.sort_by_cached_key(|a| a.name.to_lowercase());
+
+        let mut categories = super::categories_of(&apps);
+        categories.sort();
+        (categories, apps)
+    }
+
+    fn split_query<'q>(&self, query: &'q str) -> (Option<&'q str>, &'q str) {
+        match query.split_once(':') {
+            Some((section, rest)) if section.starts_with(|c: char| c.is_ascii_digit())
+                && section.chars().all(|c| c.is_ascii_alphanumeric()) =>
+            {
+                (Some(section), rest)
+            }
+            _ => (None, query),
+        }
+    }
+}
+
+/// Directories to index: `$MANPATH`, else what `manpath` reports, else the usual locations
+fn manpath() -> Vec<String> {
+    let path = std::env::var("MANPATH").ok()
+        .filter(|p| !p.trim_matches(':').is_empty())
+        .or_else(|| {
+            let output = Command::new("manpath").stderr(Stdio::null()).output().ok()?;
+            output.status.success().then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        })
+        .unwrap_or_else(|| "/usr/local/share/man:/usr/share/man".to_string());
+
+    path.split(':').filter(|p| !p.is_empty()).map(str::to_string).collect()
+}
+
+/// `(name, section)` of every page in the `man*` directories under `dir`
+fn index_dir(dir: &Path) -> Vec<(String, String)> {
+    let mut pages = Vec::new();
+    let Ok(sections) = fs::read_dir(dir) else {
+        return pages;
+    };
+
+    for section_dir in sections.flatten() {
+        let dir_name = section_dir.file_name().to_string_lossy().into_owned();
+        let Some(dir_section) = dir_name.strip_prefix("man") else {
+            continue;
+        };
+        let Ok(files) = fs::read_dir(section_dir.path()) else {
+            continue;
+        };
+
+        for file in files.flatten() {
+            let file_name = file.file_name().to_string_lossy().into_owned();
+            let stem = COMPRESSED.iter()
+                .find_map(|ext| file_name.strip_suffix(ext))
+                .unwrap_or(&file_name);
+            // printf.3p.gz lives in man3: the section in the file name is the precise one
+            match stem.rsplit_once('.') {
+                Some((name, section)) if !name.is_empty() && section.starts_with(dir_section) => {
+                    pages.push((name.to_string(), section.to_string()));
+                }
+                _ => {}
+            }
+        }
+    }
+
+    pages
+}
+
+/// `(name, section, description)` for every page the whatis database knows
+fn whatis() -> Vec<(String, String, String)> {
+    Command::new("whatis")
+        .args(["-w", "*"])
+        .stderr(Stdio::null())
+        .output()
+        .map(|output| parse_whatis(&String::from_utf8_lossy(&output.stdout)))
+        .unwrap_or_default()
+}
+
+/// Parse lines like `printf (3)           - formatted output conversion`
+fn parse_whatis(output: &str) -> Vec<(String, String, String)> {
+    output.lines()
+        .filter_map(|line| {
+            let (page, description) = line.split_once(" - ")?;
+            let (name, section) = page.trim().split_once(" (")?;
+            let section = section.strip_suffix(')')?;
+            Some((name.trim().to_string(), section.to_string(), description.trim().to_string()))
+        })
+        .collect()
+}
+
+/// A page, opened with `man <section> <name>` in the terminal. Names come from file names, so
+/// both are quoted.
+fn page_entry(name: &str, section: String, description: Option<String>) -> AppEntry {
+    AppEntry {
+        name: format!("{}({})", name, section),
+        exec: format!("man {} {}", shell_quote(&section), shell_quote(name)),
+        terminal: true,
+        description,
+        category: section,
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_index_dir() {
+        let dir = std::env::temp_dir().join(format!("dstl-man-test-{}", std::process::id()));
+        fs::create_dir_all(dir.join("man1")).unwrap();
+        fs::create_dir_all(dir.join("man3")).unwrap();
+        fs::create_dir_all(dir.join("de/man1")).unwrap();
+        for file in ["man1/ls.1.gz", "man1/git-log.1", "man3/printf.3.gz", "man3/printf.3p.gz", "man3/README", "de/man1/ls.1.gz"] {
+            fs::write(dir.join(file), "").unwrap();
+        }
+
+        let mut pages = index_dir(&dir);
+        fs::remove_dir_all(&dir).unwrap();
+        pages.sort();
+
+        let expected = [("git-log", "1"), ("ls", "1"), ("printf", "3"), ("printf", "3p")];
+        assert_eq!(pages, expected.map(|(n, s)| (n.to_string(), s.to_string())));
+    }
+
+    #[test]
+    fn test_page_entry() {
+        let entry = page_entry("std::vector", "3cxx".to_string(), None);
+        assert_eq!(entry.name, "std::vector(3cxx)");
+        assert_eq!(entry.category, "3cxx");
+        assert_eq!(page_entry("$(reboot)", "1".to_string(), None).exec, "man '1' '$(reboot)'");
+    }
+
+    #[test]
+    fn test_section_prefix() {
+        assert_eq!(ManPages.split_query("3:printf"), (Some("3"), "printf"));
+        assert_eq!(ManPages.split_query("1p:ls"), (Some("1p"), "ls"));
+        assert_eq!(ManPages.split_query("printf"), (None, "printf"));
+        assert_eq!(ManPages.split_query("std::vector"), (None, "std::vector"));
+        assert_eq!(ManPages.split_query("3 :x"), (None, "3 :x"));
+    }
+
+    #[test]
+    fn test_parse_whatis() {
+        let parsed = parse_whatis("printf (3)           - formatted output conversion\nls (1) - list directory contents\n");
+        assert_eq!(parsed[0], ("printf".to_string(), "3".to_string(), "formatted output conversion".to_string()));
+        assert_eq!(parsed.len(), 2);
+    }
+}
@@ -0,0 +1,270 @@
+//! Entry sources: everything dstl can list, from desktop apps to sway windows.
+//!
+//! A source loads its entries (and the categories they fall into) and may add entries derived
+//! from the query or take over selection. What activating an entry does is carried by the
+//! entry's own `Action`. To add a source, implement `Source` and give it a name in `build`.
+
+mod bindings;
+mod bookmarks;
+mod combi;
+mod desktop;
+mod emoji;
+mod files;
+mod man;
+mod pass;
+mod path;
+mod process;
+mod projects;
+mod recent;
+mod script;
+mod session;
+mod ssh;
+mod steam;
+mod sway;
+mod tmux;
+
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::sync::mpsc::Receiver;
+use crate::app::AppEntry;
+use crate::config::DstlConfig;
+
+pub use desktop::DesktopApps;
+pub use process::send_signal;
+
+/// Categories and entries, in display order
+pub type Listing = (Vec<String>, Vec<AppEntry>);
+
+/// What a source gets to see while loading or reacting to the user
+pub struct Context<'a> {
+    pub config: &'a DstlConfig,
+    /// Sway window that had focus before dstl opened
+    pub previous_window: Option<i64>,
+}
+
+pub trait Source {
+    /// Name used in `sources`, `--mode` and recent lists
+    fn name(&self) -> &str;
+
+    /// Load the entries to list. Entries are shown in the order returned.
+    fn load(&self, ctx: &Context) -> Listing;
+
+    /// Whether the source needs `Context::previous_window`, which costs a sway IPC round trip
+    fn uses_previous_window(&self) -> bool {
+        false
+    }
+
+    /// Whether rows should be tagged with the source of each entry (`AppEntry::source`)
+    fn shows_badges(&self) -> bool {
+        false
+    }
+
+    /// Split a category filter off the query, e.g. `3:printf` for man section 3. Only entries
+    /// whose category starts with the filter are searched for the rest.
+    fn split_query<'q>(&self, query: &'q str) -> (Option<&'q str>, &'q str) {
+        (None, query)
+    }
+
+    /// Whether launching `entry` counts towards the recent apps list. Sources that aren't apps
+    /// keep their own history if they need one, so their entries don't push out real apps.
+    fn records_recent(&self, _entry: &AppEntry) -> bool {
+        false
+    }
+
+    /// Extra entries derived from the query, like one creating what it names. They are
+    /// listed after the results, so a query that matches something still selects that first.
+    fn offers(&self, _query: &str, _apps: &[AppEntry], _ctx: &Context) -> Vec<AppEntry> {
+        Vec::new()
+    }
+
+    /// Entries found in the background after `load`, for sources too slow to list up front.
+    /// Batches sent on the channel are appended to the list as they arrive; the sender should
+    /// stop once a send fails, which means the list was closed or replaced.
+    fn stream(&self, _ctx: &Context) -> Option<Receiver<Vec<AppEntry>>> {
+        None
+    }
+
+    /// Called when an entry is chosen. Returning a listing keeps the menu open with it instead
+    /// of quitting and performing the entry's action.
+    fn select(&self, _entry: &AppEntry, _ctx: &Context) -> Option<Listing> {
+        None
+    }
+}
+
+/// Build a source by name: a built-in one, or a script from the config
+pub fn build(name: &str, config: &DstlConfig) -> Option<Rc<dyn Source>> {
+    let source: Rc<dyn Source> = match name {
+        "apps" => Rc::new(DesktopApps),
+        "dmenu" => Rc::new(path::PathBinaries),
+        "windows" => Rc::new(sway::Windows),
+        "workspaces" => Rc::new(sway::Workspaces),
+        "scratchpad" => Rc::new(sway::Scratchpad),
+        "session" => Rc::new(session::Session),
+        "kill" => Rc::new(process::Processes),
+        "man" => Rc::new(man::ManPages),
+        "pass" => Rc::new(pass::PasswordStore),
+        "recent" => Rc::new(recent::RecentFiles),
+        "files" => Rc::new(files::Files),
+        "projects" => Rc::new(projects::Projects),
+        "emoji" => Rc::new(emoji::EmojiPicker),
+        "bookmarks" => Rc::new(bookmarks::Bookmarks),
+        "steam" => Rc::new(steam::SteamGames),
+        "tmux" => Rc::new(tmux::TmuxSessions),
+        "bindings" => Rc::new(bindings::Bindings),
+        "ssh" => Rc::new(ssh::SshHosts),
+        "combi" => Rc::new(combi::Combi {
+            sources: config.combi.iter()
+                .filter(|name| *name != "combi")
+                .filter_map(|name| build(name, config))
+                .collect(),
+        }),
+        _ => {
+            let script = config.scripts.iter().find(|s| s.name == name)?;
+            Rc::new(script::Script(script.clone()))
+        }
+    };
+    Some(source)
+}
+
+/// The sources enabled in the config, in cycling order. Unknown names are skipped.
+pub fn enabled(config: &DstlConfig) -> Vec<Rc<dyn Source>> {
+    config.sources.iter()
+        .filter_map(|name| build(name, config))
+        .collect()
+}
+
+/// Distinct categories of the given entries, in order of first appearance
+pub fn categories_of(apps: &[AppEntry]) -> Vec<String> {
+    let mut categories: Vec<String> = Vec::new();
+    for app in apps {
+        if !categories.contains(&app.category) {
+            categories.push(app.category.clone());
+        }
+    }
+    categories
+}
+
+/// A path from the config, with a leading `~` standing for the home directory
+fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix('~'), dirs::home_dir()) {
+        (Some(rest), Some(home)) if rest.is_empty() || rest.starts_with('/') => {
+            home.join(rest.trim_start_matches('/'))
+        }
+        _ => PathBuf::from(path),
+    }
+}
+
+/// A path for display, with the home directory shortened to `~`
+fn tilde(path: &Path, home: Option<&Path>) -> String {
+    match home.and_then(|home| path.strip_prefix(home).ok()) {
+        Some(rest) if rest.as_os_str().is_empty() => "~".to_string(),
+        Some(rest) => format!("~/{}", rest.to_string_lossy()),
+        None => path.to_string_lossy().into_owned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{ScriptConfig, ScriptInput, test_config};
+
+    #[test]
+    fn test_enabled_sources_follow_config_order() {
+        let mut config = test_config();
+        config.scripts.push(ScriptConfig {
+            name: "vpn".to_string(),
+            exec: "true".to_string(),
+            input: ScriptInput::Argument,
+        });
+        config.sources = ["vpn", "nonsense", "windows", "apps"].map(String::from).to_vec();
+
+        let names: Vec<String> = enabled(&config).iter().map(|s| s.name().to_string()).collect();
+        assert_eq!(names, ["vpn", "windows", "apps"]);
+    }
+
+    #[test]
+    fn test_combi_tags_entries_with_their_source() {
+        let mut config = test_config();
+        config.entries.push(crate::config::CustomEntry {
+            name: "Weather".to_string(),
+            exec: "curl wttr.in".to_string(),
+            category: "Custom".to_string(),
+            terminal: true,
+            icon: None,
+        });
+        // The script is a file, so the selection reaches it only as an argument
+        let script = std::env::temp_dir().join(format!("dstl-combi-numbers-{}", std::process::id()));
+        std::fs::write(&script, "#!/bin/sh\nif [ -n \"$1\" ]; then echo \"picked $1\"; else printf 'one\\ntwo\\n'; fi\n").unwrap();
+        std::fs::set_permissions(&script, std::os::unix::fs::PermissionsExt::from_mode(0o755)).unwrap();
+        config.scripts.push(ScriptConfig {
+            name: "numbers".to_string(),
+            exec: script.to_string_lossy().into_owned(),
+            input: ScriptInput::Argument,
+        });
+        config.combi = ["numbers", "combi"].map(String::from).to_vec();
+
+        let combi = build("combi", &config).unwrap();
+        assert!(combi.shows_badges());
+
+        let ctx = Context { config: &config, previous_window: None };
+        let (_, apps) = combi.load(&ctx);
+        let tagged: Vec<(&str, &str)> = apps.iter().map(|a| (a.source.as_str(), a.name.as_str())).collect();
+        assert_eq!(tagged, [("numbers", "one"), ("numbers", "two")]);
+
+        // The selection goes back to the script, and its reply stays tagged
+        let (_, reply) = combi.select(&apps[1], &ctx).unwrap();
+        assert_eq!(reply[0].name, "picked two");
+        assert_eq!(reply[0].source, "numbers");
+        std::fs::remove_file(&script).unwrap();
+
+        assert_eq!(config.entries[0].to_app_entry().source, "custom");
+    }
+
+    #[test]
+    fn test_only_apps_are_recent() {
+        let mut config = test_config();
+        config.combi = ["apps", "ssh"].map(String::from).to_vec();
+        let entry = |source: &str| AppEntry { name: "x".to_string(), source: source.to_string(), ..Default::default() };
+
+        assert!(build("apps", &config).unwrap().records_recent(&entry("")));
+        assert!(build("dmenu", &config).unwrap().records_recent(&entry("")));
+        assert!(!build("ssh", &config).unwrap().records_recent(&entry("")));
+        // Password entry names would end up in plain text on disk
+        assert!(!build("pass", &config).unwrap().records_recent(&entry("")));
+        assert!(!build("session", &config).unwrap().records_recent(&entry("")));
+
+        let combi = build("combi", &config).unwrap();
+        assert!(combi.records_recent(&entry("apps")));
+        assert!(combi.records_recent(&entry("custom")));
+        assert!(!combi.records_recent(&entry("ssh")));
+    }
+
+    #[test]
+    fn test_combi_delegates_query_handling() {
+        let mut config = test_config();
+        config.combi = ["man", "tmux"].map(String::from).to_vec();
+        let combi = build("combi", &config).unwrap();
+        let ctx = Context { config: &config, previous_window: None };
+
+        assert_eq!(combi.split_query("3:printf"), (Some("3"), "printf"));
+        assert_eq!(combi.split_query("printf"), (None, "printf"));
+
+        // An entry of another source with the same name doesn't hide the tmux row
+        let other = AppEntry { name: "scratch".to_string(), source: "man".to_string(), ..Default::default() };
+        let offers = combi.offers("scratch", std::slice::from_ref(&other), &ctx);
+        assert_eq!(offers.len(), 1);
+        assert_eq!(offers[0].name, "Create session \"scratch\"");
+        assert_eq!(offers[0].source, "tmux");
+
+        // but it is listed after that entry, which stays selected
+        config.tmux_command = "false".to_string();
+        let mut app = crate::app::App::new("combi", crate::app::Mode::SinglePane, &config);
+        app.set_entries(vec!["1".to_string()], vec![other]);
+        app.input = "scratch".into();
+        app.refresh_pinned();
+        assert_eq!(app.row_count(), 2);
+        assert_eq!(app.selected_entry().map(|e| e.source.as_str()), Some("man"));
+        app.selected_app = 1;
+        assert_eq!(app.selected_entry().map(|e| e.source.as_str()), Some("tmux"));
+    }
+}
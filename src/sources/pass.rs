@@ -0,0 +1,116 @@
+//! Entries of the password store used by `pass`
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use nerd_font_symbols::fa;
+use crate::app::{Action, AppEntry};
+use crate::launch::shell_quote;
+use super::{Context, Listing, Source};
+
+/// Password store entries (`pass`), like passmenu. Enter prints the entry name, or runs
+/// `pass.command` with `{}` replaced by it.
+pub struct PasswordStore;
+
+impl Source for PasswordStore {
+    fn name(&self) -> &str {
+        "pass"
+    }
+
+    fn load(&self, ctx: &Context) -> Listing {
+        let Some(store) = store_dir() else {
+            return (Vec::new(), Vec::new());
+        };
+
+        let mut names = Vec::new();
+        collect_entries(&store, &store, &mut names);
+        names.sort_by_cached_key(|n| n.to_lowercase());
+
+        let apps = names.into_iter().map(|name| to_app_entry(name, &ctx.config.pass_command)).collect();
+        (vec!["Passwords".to_string()], apps)
+    }
+}
+
+/// Print the name when no command is configured, otherwise run the command for it
+fn to_app_entry(name: String, command: &str) -> AppEntry {
+    let (exec, action) = if command.is_empty() {
+        (String::new(), Action::Print(name.clone()))
+    } else {
+        (command.replace("{}", &shell_quote(&name)), Action::Launch)
+    };
+
+    AppEntry {
+        name,
+        category: "Passwords".to_string(),
+        exec,
+        icon: Some(fa::FA_KEY.to_string()),
+        action,
+        ..Default::default()
+    }
+}
+
+/// `$PASSWORD_STORE_DIR`, else `~/.password-store`
+fn store_dir() -> Option<PathBuf> {
+    match std::env::var_os("PASSWORD_STORE_DIR") {
+        Some(dir) if !dir.is_empty() => Some(PathBuf::from(dir)),
+        _ => dirs::home_dir().map(|h| h.join(".password-store")),
+    }
+}
+
+/// Paths of the `*.gpg` files under `dir` relative to the store, without the extension.
+/// Hidden files and directories (`.git`, `.extensions`) are skipped.
+fn collect_entries(store: &Path, dir: &Path, names: &mut Vec<String>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if entry.file_name().to_string_lossy().starts_with('.') {
+            continue;
+        }
+        // Not following symlinked directories avoids looping forever on a cycle
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+
+        if file_type.is_dir() {
+            collect_entries(store, &path, names);
+        } else if path.extension().is_some_and(|ext| ext == "gpg")
+            && let Ok(relative) = path.with_extension("").strip_prefix(store)
+        {
+            names.push(relative.to_string_lossy().into_owned());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_collect_entries() {
+        let store = std::env::temp_dir().join(format!("dstl-pass-test-{}", std::process::id()));
+        fs::create_dir_all(store.join("work/servers")).unwrap();
+        fs::create_dir_all(store.join(".git")).unwrap();
+        for file in ["email.gpg", "work/servers/db.gpg", "work/notes.txt", ".gpg-id", ".git/x.gpg"] {
+            fs::write(store.join(file), "").unwrap();
+        }
+
+        let mut names = Vec::new();
+        collect_entries(&store, &store, &mut names);
+        fs::remove_dir_all(&store).unwrap();
+        names.sort();
+
+        assert_eq!(names, ["email", "work/servers/db"]);
+    }
+
+    #[test]
+    fn test_entry_actions() {
+        let entry = to_app_entry("bank".to_string(), "");
+        assert_eq!(entry.action, Action::Print("bank".to_string()));
+
+        let entry = to_app_entry("work/it's".to_string(), "pass -c {}");
+        assert_eq!(entry.action, Action::Launch);
+        assert_eq!(entry.exec, "pass -c 'work/it'\\''s'");
+    }
+}
@@ -0,0 +1,112 @@
+//! Executables from PATH directories, dmenu style
+
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+use crate::app::AppEntry;
+use super::{Context, Listing, Source};
+
+/// Executables in /usr/bin (`dmenu`)
+pub struct PathBinaries;
+
+impl Source for PathBinaries {
+    fn name(&self) -> &str {
+        "dmenu"
+    }
+
+    fn records_recent(&self, _entry: &AppEntry) -> bool {
+        true
+    }
+
+    fn load(&self, _ctx: &Context) -> Listing {
+        let (categories, mut apps) = load_from_path("/usr/bin");
+        for app in &mut apps {
+            app.terminal |= app.needs_terminal();
+        }
+        apps.sort_by_cached_key(|a| a.name.to_lowercase());
+        (categories, apps)
+    }
+}
+
+/// Load executables from a directory (dmenu style)
+fn load_from_path<P: AsRef<Path>>(path: P) -> (Vec<String>, Vec<AppEntry>) {
+    let mut apps = Vec::new();
+    let gui_bins = get_known_gui_binaries();
+
+    if let Ok(entries) = fs::read_dir(path) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_file()
+                && let Some(name) = path.file_name().and_then(|s| s.to_str())
+            {
+                let is_gui = gui_bins.contains(name);
+                apps.push(AppEntry {
+                    name: name.to_string(),
+                    category: "CLI".to_string(),
+                    exec: name.to_string(),
+                    terminal: !is_gui,
+                    ..Default::default()
+                });
+            }
+        }
+    }
+
+    // Dmenu-style uses CLI category for consistency
+    (vec!["CLI".to_string()], apps)
+}
+
+/// Scan desktop files to find binaries that are GUI applications (Terminal=false)
+fn get_known_gui_binaries() -> HashSet<String> {
+    let mut gui_bins = HashSet::new();
+    let home = std::env::var("HOME").unwrap_or_else(|_| String::from("/home"));
+    let local_dir = format!("{}/.local/share/applications", home);
+    let paths = vec![local_dir, "/usr/share/applications".to_string()];
+
+    for dir in paths {
+        if let Ok(entries) = fs::read_dir(&dir) {
+            for entry in entries.flatten() {
+                let path = entry.path();
+                if path.extension().and_then(|s| s.to_str()) != Some("desktop") {
+                    continue;
+                }
+                if let Ok(content) = fs::read_to_string(&path) {
+                    let mut exec = None;
+                    let mut terminal = false;
+                    let mut in_desktop_entry = false;
+                    
+                    for line in content.lines() {
+                        let line = line.trim();
+                        if line.starts_with('[') {
+                            in_desktop_entry = line == "[Desktop Entry]";
+                            continue;
+                        }
+                        if !in_desktop_entry { continue; }
+                        
+                        if let Some((key, value)) = line.split_once('=') {
+                            let key = key.trim();
+                            let value = value.trim();
+                            match key {
+                                "Exec" => exec = Some(value.to_string()),
+                                "Terminal" => terminal = value == "true",
+                                _ => {}
+                            }
+                        }
+                    }
+                    
+                    if let Some(exec_str) = exec
+                        && !terminal
+                    {
+                        let clean = super::desktop::clean_exec(&exec_str);
+                        if let Some(bin) = clean.split_whitespace().next() {
+                            let bin_path = Path::new(bin);
+                            if let Some(name) = bin_path.file_name().and_then(|s| s.to_str()) {
+                                gui_bins.insert(name.to_string());
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+    gui_bins
+}
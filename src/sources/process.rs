@@ -0,0 +1,159 @@
+//! The current user's processes, read from /proc, for sending signals
+
+use std::fs;
+use std::os::unix::fs::MetadataExt;
+use eyre::{Result, eyre};
+use crate::app::{Action, AppEntry};
+use super::{Context, Listing, Source};
+
+/// Running processes owned by the current user, biggest first (`kill`). Enter sends SIGTERM,
+/// the alternate action SIGKILL after confirmation.
+pub struct Processes;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Process {
+    pid: i32,
+    name: String,
+    /// Resident set size in bytes
+    rss: u64,
+    cmdline: String,
+}
+
+impl Source for Processes {
+    fn name(&self) -> &str {
+        "kill"
+    }
+
+    fn load(&self, _ctx: &Context) -> Listing {
+        // SAFETY: getuid and sysconf take no pointers and have no preconditions. getuid can't
+        // fail; a failed sysconf returns -1, which max(1) turns into a usable page size.
+        let uid = unsafe { libc::getuid() };
+        let page_size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) }.max(1) as u64;
+        let own_pid = std::process::id() as i32;
+
+        let mut processes: Vec<Process> = fs::read_dir("/proc")
+            .into_iter()
+            .flatten()
+            .flatten()
+            .filter_map(|entry| {
+                let pid: i32 = entry.file_name().to_str()?.parse().ok()?;
+                if pid == own_pid || entry.metadata().ok()?.uid() != uid {
+                    return None;
+                }
+                let dir = entry.path();
+                let stat = fs::read_to_string(dir.join("stat")).ok()?;
+                let cmdline = fs::read(dir.join("cmdline")).unwrap_or_default();
+                parse_process(pid, &stat, &cmdline, page_size)
+            })
+            .collect();
+        processes.sort_by_key(|p| std::cmp::Reverse(p.rss));
+
+        let width = processes.iter().map(|p| p.name.chars().count()).max().unwrap_or(0);
+        let apps = processes.iter()
+            .map(|p| AppEntry {
+                name: format!(
+                    "{:<width$}  {:>7}  {:>9}  {}",
+                    p.name, p.pid, format_size(p.rss), p.cmdline,
+                    width = width
+                ),
+                category: "Processes".to_string(),
+                action: Action::Signal(p.pid, p.name.clone(), libc::SIGTERM),
+                alt_action: Some(Action::Signal(p.pid, p.name.clone(), libc::SIGKILL)),
+                alt_confirm: Some(format!("Kill {} ({}) with SIGKILL?", p.name, p.pid)),
+                ..Default::default()
+            })
+            .collect();
+
+        (vec!["Processes".to_string()], apps)
+    }
+}
+
+/// Parse `/proc/<pid>/stat` and the NUL-separated `cmdline`. Kernel threads, which have no
+/// command line, are skipped.
+fn parse_process(pid: i32, stat: &str, cmdline: &[u8], page_size: u64) -> Option<Process> {
+    // The name is parenthesised and may itself contain spaces and parentheses
+    let name = &stat[stat.find('(')? + 1..stat.rfind(')')?];
+    let fields: Vec<&str> = stat[stat.rfind(')')? + 1..].split_whitespace().collect();
+    // Fields after the name start at field 3 (state); rss is field 24, in pages
+    let rss: u64 = fields.get(21)?.parse().ok()?;
+
+    let cmdline = String::from_utf8_lossy(cmdline)
+        .split('\0')
+        .filter(|arg| !arg.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ");
+    if cmdline.is_empty() {
+        return None;
+    }
+
+    Some(Process { pid, name: name.to_string(), rss: rss * page_size, cmdline })
+}
+
+/// Send `signal` to `pid`, provided it is still the process listed as `name`. The pid may have
+/// been reused by something else since the list was read.
+pub fn send_signal(pid: i32, name: &str, signal: i32) -> Result<()> {
+    let comm = fs::read_to_string(format!("/proc/{}/comm", pid))
+        .map_err(|_| eyre!("process {} ({}) has already exited", pid, name))?;
+    if comm.trim_end_matches('\n') != name {
+        return Err(eyre!("process {} is no longer {}", pid, name));
+    }
+
+    // SAFETY: kill(2) takes plain integers and has no memory-safety preconditions. pid is a
+    // positive pid read from /proc, so this never signals a process group or every process.
+    if unsafe { libc::kill(pid, signal) } != 0 {
+        return Err(eyre!("couldn't signal {} ({}): {}", pid, name, std::io::Error::last_os_error()));
+    }
+    Ok(())
+}
+
+fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit + 1 < UNITS.len() {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} {}", bytes, UNITS[0])
+    } else {
+        format!("{:.1} {}", size, UNITS[unit])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_process() {
+        let stat = "4242 (Web Content (x)) S 1 4242 4242 0 -1 4194560 100 0 0 0 5 3 0 0 20 0 30 0 \
+                    1234 123456789 2560 18446744073709551615 1 1 0 0 0 0 0 4096 0 0 0 0 17 3 0 0 0 0 0";
+        let process = parse_process(4242, stat, b"/usr/lib/firefox/firefox\0-contentproc\0", 4096).unwrap();
+        assert_eq!(process.name, "Web Content (x)");
+        assert_eq!(process.rss, 2560 * 4096);
+        assert_eq!(process.cmdline, "/usr/lib/firefox/firefox -contentproc");
+
+        // Kernel threads have an empty command line
+        assert!(parse_process(2, "2 (kthreadd) S 0 0 0 0 -1 0 0 0 0 0 0 0 0 0 20 0 1 0 1 0 0", b"", 4096).is_none());
+    }
+
+    #[test]
+    fn test_send_signal_checks_name() {
+        let mut child = std::process::Command::new("sleep").arg("30").spawn().unwrap();
+        let pid = child.id() as i32;
+
+        // A recycled pid running something else is left alone
+        assert!(send_signal(pid, "not-sleep", libc::SIGTERM).is_err());
+        assert!(child.try_wait().unwrap().is_none());
+
+        send_signal(pid, "sleep", libc::SIGTERM).unwrap();
+        assert!(!child.wait().unwrap().success());
+    }
+
+    #[test]
+    fn test_format_size() {
+        assert_eq!(format_size(512), "512 B");
+        assert_eq!(format_size(10 * 1024 * 1024), "10.0 MiB");
+    }
+}
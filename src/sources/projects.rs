@@ -0,0 +1,133 @@
+//! Git repositories under the configured roots, ranked by frecency
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use nerd_font_symbols::fa;
+use crate::app::AppEntry;
+use crate::frecency::{self, Frecency};
+use super::{Context, Listing, Source};
+
+/// Where project visits are counted, in dstl's cache directory
+const FRECENCY_FILE: &str = "projects.json";
+
+/// Directories containing `.git` under `projects.roots` (`projects`), most frecent first.
+/// Enter runs `projects.command` for the project.
+pub struct Projects;
+
+impl Source for Projects {
+    fn name(&self) -> &str {
+        "projects"
+    }
+
+    fn load(&self, ctx: &Context) -> Listing {
+        let config = &ctx.config.projects;
+        let mut repos = Vec::new();
+        for root in &config.roots {
+            find_repos(&super::expand_home(root), config.max_depth, &mut repos);
+        }
+
+        let home = dirs::home_dir();
+        let frecency = Frecency::load(FRECENCY_FILE);
+        let now = frecency::now();
+
+        let mut apps: Vec<(f64, AppEntry)> = repos.iter()
+            .map(|path| {
+                let display = super::tilde(path, home.as_deref());
+                let full = path.to_string_lossy().into_owned();
+                let exec = crate::launch::expand_template(&config.command, ctx.config, &[("path", &full)]);
+                let entry = AppEntry {
+                    name: path.file_name().unwrap_or_default().to_string_lossy().into_owned(),
+                    category: "Projects".to_string(),
+                    exec,
+                    icon: Some(fa::FA_CODE_BRANCH.to_string()),
+                    // The path comes first, as the key `select` counts visits under
+                    keywords: vec![full.clone(), display.clone()],
+                    description: Some(display),
+                    ..Default::default()
+                };
+                (frecency.score(&full, now), entry)
+            })
+            .collect();
+        // Most frecent first, the rest by name
+        apps.sort_by(|(a_score, a), (b_score, b)| {
+            b_score.total_cmp(a_score).then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase()))
+        });
+
+        (vec!["Projects".to_string()], apps.into_iter().map(|(_, entry)| entry).collect())
+    }
+
+    fn select(&self, entry: &AppEntry, _ctx: &Context) -> Option<Listing> {
+        // Count the visit; the command still runs
+        if let Some(path) = entry.keywords.first() {
+            let _ = Frecency::load(FRECENCY_FILE).record(path);
+        }
+        None
+    }
+}
+
+/// Collect the directories under `dir` that contain `.git` (a directory, or a file for
+/// worktrees and submodules), up to `depth` levels down. Repositories aren't searched further.
+fn find_repos(dir: &Path, depth: usize, repos: &mut Vec<PathBuf>) {
+    if dir.join(".git").exists() {
+        repos.push(dir.to_path_buf());
+        return;
+    }
+    if depth == 0 {
+        return;
+    }
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+
+    let mut subdirs: Vec<PathBuf> = entries.flatten()
+        .filter(|e| e.file_type().is_ok_and(|t| t.is_dir()))
+        .filter(|e| !e.file_name().to_string_lossy().starts_with('.'))
+        .map(|e| e.path())
+        .collect();
+    subdirs.sort();
+    for subdir in subdirs {
+        find_repos(&subdir, depth - 1, repos);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_repos() {
+        let root = std::env::temp_dir().join(format!("dstl-projects-test-{}", std::process::id()));
+        for dir in ["src/dstl/.git", "src/dstl/vendor/lib/.git", "src/work/api/.git", "src/too/deep/repo/.git", ".hidden/.git"] {
+            fs::create_dir_all(root.join(dir)).unwrap();
+        }
+        fs::create_dir_all(root.join("src/worktree")).unwrap();
+        fs::write(root.join("src/worktree/.git"), "gitdir: /elsewhere\n").unwrap();
+
+        let mut repos = Vec::new();
+        find_repos(&root, 3, &mut repos);
+        fs::remove_dir_all(&root).unwrap();
+
+        let found: Vec<String> = repos.iter()
+            .map(|p| p.strip_prefix(&root).unwrap().to_string_lossy().into_owned())
+            .collect();
+        assert_eq!(found, ["src/dstl", "src/work/api", "src/worktree"]);
+    }
+
+    #[test]
+    fn test_entries_keep_the_path() {
+        let root = std::env::temp_dir().join(format!("dstl-projects-load-{}", std::process::id()));
+        fs::create_dir_all(root.join("dstl/.git")).unwrap();
+
+        let mut config = crate::config::test_config();
+        config.projects.roots = vec![root.to_string_lossy().into_owned()];
+        config.projects.command = "code {path}".to_string();
+        let (_, apps) = Projects.load(&Context { config: &config, previous_window: None });
+        fs::remove_dir_all(&root).unwrap();
+
+        // Visits are counted under the real path, whatever the description shows
+        let path = root.join("dstl").to_string_lossy().into_owned();
+        assert_eq!(apps[0].name, "dstl");
+        assert_eq!(apps[0].keywords.first(), Some(&path));
+        assert_eq!(apps[0].exec, format!("code {}", crate::launch::shell_quote(&path)));
+    }
+}
@@ -0,0 +1,242 @@
+//! Recently used files from GTK's `recently-used.xbel`
+
+use std::fs;
+use std::path::Path;
+use nerd_font_symbols::fa;
+use crate::app::AppEntry;
+use super::{Context, Listing, Source};
+
+/// Files recently opened in GTK applications (`recent`), newest first. Enter opens the file
+/// with its default application.
+pub struct RecentFiles;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Bookmark {
+    path: String,
+    /// ISO 8601 timestamp in UTC, e.g. `2024-05-01T10:22:33.123456Z`
+    modified: String,
+    mime: Option<String>,
+}
+
+impl Source for RecentFiles {
+    fn name(&self) -> &str {
+        "recent"
+    }
+
+    fn load(&self, _ctx: &Context) -> Listing {
+        let Some(data) = dirs::data_dir() else {
+            return (Vec::new(), Vec::new());
+        };
+        let content = fs::read_to_string(data.join("recently-used.xbel")).unwrap_or_default();
+
+        let mut bookmarks: Vec<Bookmark> = parse_xbel(&content).into_iter()
+            .filter(|b| Path::new(&b.path).exists())
+            .collect();
+        bookmarks.sort_by(|a, b| b.modified.cmp(&a.modified));
+
+        let home = dirs::home_dir();
+        let apps = bookmarks.iter()
+            .map(|b| {
+                let path = Path::new(&b.path);
+                let name = path.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+                let dir = path.parent().map(|p| super::tilde(p, home.as_deref())).unwrap_or_default();
+                let opener = crate::mime::open_command(b.mime.as_deref(), &b.path);
+
+                AppEntry {
+                    name,
+                    category: "Recent Files".to_string(),
+                    exec: opener.exec,
+                    terminal: opener.terminal,
+                    icon: Some(fa::FA_FILE.to_string()),
+                    description: Some(format!("{}  {}", dir, local_time(&b.modified))),
+                    keywords: vec![dir],
+                    ..Default::default()
+                }
+            })
+            .collect();
+
+        (vec!["Recent Files".to_string()], apps)
+    }
+}
+
+/// The local `file://` bookmarks in an XBEL document. Only the bits GTK writes are handled:
+/// `<bookmark href=".." modified="..">` elements with a `<mime:mime-type type=".."/>` inside.
+fn parse_xbel(content: &str) -> Vec<Bookmark> {
+    let mut bookmarks = Vec::new();
+    let mut rest = content;
+
+    while let Some(start) = rest.find("<bookmark ") {
+        rest = &rest[start..];
+        let Some(tag_end) = rest.find('>') else {
+            break;
+        };
+        let tag = &rest[..tag_end];
+        // The body runs to the closing tag, unless the element is self-closing
+        let body_end = if tag.ends_with('/') {
+            tag_end
+        } else {
+            rest.find("</bookmark>").unwrap_or(rest.len())
+        };
+        let body = &rest[tag_end..body_end];
+        rest = &rest[body_end..];
+
+        let Some(path) = attribute(tag, "href").and_then(|href| file_path(&href)) else {
+            continue;
+        };
+        let modified = attribute(tag, "modified").unwrap_or_default();
+        let mime = body.find("<mime:mime-type ").and_then(|i| {
+            let tag = &body[i..];
+            attribute(&tag[..tag.find('>').unwrap_or(tag.len())], "type")
+        });
+
+        bookmarks.push(Bookmark { path, modified, mime });
+    }
+
+    bookmarks
+}
+
+/// The unescaped value of `name="..."` in a start tag
+fn attribute(tag: &str, name: &str) -> Option<String> {
+    let needle = format!("{}=\"", name);
+    let start = tag.match_indices(&needle)
+        .find(|(i, _)| tag[..*i].ends_with(char::is_whitespace))
+        .map(|(i, _)| i + needle.len())?;
+    let end = start + tag[start..].find('"')?;
+    Some(unescape(&tag[start..end]))
+}
+
+/// Replace the predefined XML entities and numeric character references
+fn unescape(text: &str) -> String {
+    let mut result = String::new();
+    let mut rest = text;
+
+    while let Some(amp) = rest.find('&') {
+        result.push_str(&rest[..amp]);
+        rest = &rest[amp..];
+        let Some(semi) = rest.find(';') else {
+            break;
+        };
+        let entity = &rest[1..semi];
+        let replacement = match entity {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            _ => entity.strip_prefix("#x")
+                .map(|hex| u32::from_str_radix(hex, 16))
+                .or_else(|| entity.strip_prefix('#').map(str::parse))
+                .and_then(Result::ok)
+                .and_then(char::from_u32),
+        };
+        match replacement {
+            Some(c) => {
+                result.push(c);
+                rest = &rest[semi + 1..];
+            }
+            None => {
+                result.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    result.push_str(rest);
+    result
+}
+
+/// The path of a local `file://` URI, percent-decoded
+fn file_path(uri: &str) -> Option<String> {
+    let encoded = uri.strip_prefix("file://")?;
+    let bytes = encoded.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if bytes[i] == b'%'
+            && let Some(byte) = encoded.get(i + 1..i + 3).and_then(|hex| u8::from_str_radix(hex, 16).ok())
+        {
+            decoded.push(byte);
+            i += 3;
+        } else {
+            decoded.push(bytes[i]);
+            i += 1;
+        }
+    }
+
+    Some(String::from_utf8_lossy(&decoded).into_owned())
+}
+
+/// Format an XBEL UTC timestamp as local `YYYY-MM-DD HH:MM`
+fn local_time(timestamp: &str) -> String {
+    let field = |range: std::ops::Range<usize>| timestamp.get(range).and_then(|s| s.parse::<i32>().ok());
+    let (Some(year), Some(month), Some(day), Some(hour), Some(minute), Some(second)) =
+        (field(0..4), field(5..7), field(8..10), field(11..13), field(14..16), field(17..19))
+    else {
+        return timestamp.to_string();
+    };
+
+    // SAFETY: an all-zero `tm` is valid (integers and a nullable zone pointer). timegm and
+    // localtime_r only touch the locals passed to them, and localtime_r writes to `local`
+    // rather than a shared static buffer.
+    unsafe {
+        let mut utc: libc::tm = std::mem::zeroed();
+        utc.tm_year = year - 1900;
+        utc.tm_mon = month - 1;
+        utc.tm_mday = day;
+        utc.tm_hour = hour;
+        utc.tm_min = minute;
+        utc.tm_sec = second;
+        let time = libc::timegm(&mut utc);
+
+        let mut local: libc::tm = std::mem::zeroed();
+        if libc::localtime_r(&time, &mut local).is_null() {
+            return timestamp.to_string();
+        }
+        format!(
+            "{:04}-{:02}-{:02} {:02}:{:02}",
+            local.tm_year + 1900, local.tm_mon + 1, local.tm_mday, local.tm_hour, local.tm_min
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_xbel() {
+        let content = r#"<?xml version="1.0" encoding="UTF-8"?>
+<xbel version="1.0"
+      xmlns:bookmark="http://www.freedesktop.org/standards/desktop-bookmarks"
+      xmlns:mime="http://www.freedesktop.org/standards/shared-mime-info">
+  <bookmark href="file:///home/alice/Documents/Q1%20report%20&amp;%20notes.pdf" added="2024-04-30T09:00:00.000000Z" modified="2024-05-01T10:22:33.123456Z" visited="2024-05-01T10:22:33.123456Z">
+    <info>
+      <metadata owner="http://freedesktop.org">
+        <mime:mime-type type="application/pdf"/>
+        <bookmark:applications>
+          <bookmark:application name="Papers" exec="&apos;papers %u&apos;" modified="2024-05-01T10:22:33.123456Z" count="2"/>
+        </bookmark:applications>
+      </metadata>
+    </info>
+  </bookmark>
+  <bookmark href="sftp://host/remote.txt" modified="2024-05-02T00:00:00Z"/>
+  <bookmark href="file:///tmp/caf%C3%A9.txt" modified="2024-05-03T08:00:00Z"/>
+</xbel>
+"#;
+        let bookmarks = parse_xbel(content);
+        assert_eq!(bookmarks.len(), 2);
+        assert_eq!(bookmarks[0], Bookmark {
+            path: "/home/alice/Documents/Q1 report & notes.pdf".to_string(),
+            modified: "2024-05-01T10:22:33.123456Z".to_string(),
+            mime: Some("application/pdf".to_string()),
+        });
+        assert_eq!(bookmarks[1].path, "/tmp/café.txt");
+        assert_eq!(bookmarks[1].mime, None);
+    }
+
+    #[test]
+    fn test_unescape() {
+        assert_eq!(unescape("a &lt;b&gt; &amp; &#39;c&#x27; &unknown;"), "a <b> & 'c' &unknown;");
+    }
+}
@@ -0,0 +1,185 @@
+//! Script modes: external executables that provide the entry list, in the spirit of rofi's
+//! script modi.
+//!
+//! The script is run without arguments to get the initial list, one entry per line. A line is
+//! either plain text (the entry name) or a JSON object:
+//!
+//! ```text
+//! {"name": "Shutdown", "info": "poweroff", "icon": "", "category": "Power"}
+//! ```
+//!
+//! When an entry is chosen the script is run again with the entry's `info` (or its name if it
+//! has none), as the first argument or on stdin. If it prints more entries the menu stays open
+//! with the new list; if it prints nothing dstl exits.
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+use eyre::{Result, eyre};
+use serde::Deserialize;
+use crate::app::{Action, AppEntry};
+use crate::config::{ScriptConfig, ScriptInput};
+use super::{Context, Listing, Source, categories_of};
+
+/// A script from `config.scripts`, named after it
+pub struct Script(pub ScriptConfig);
+
+impl Source for Script {
+    fn name(&self) -> &str {
+        &self.0.name
+    }
+
+    // Entries stay in the order the script printed them
+    fn load(&self, _ctx: &Context) -> Listing {
+        let output = run(&self.0, None).unwrap_or_default();
+        let apps = parse_entries(&output, &self.0.name);
+        (categories_of(&apps), apps)
+    }
+
+    /// Pass the selection back; a non-empty reply replaces the list
+    fn select(&self, entry: &AppEntry, _ctx: &Context) -> Option<Listing> {
+        let Action::Script(selection) = &entry.action else {
+            return None;
+        };
+
+        let output = run(&self.0, Some(selection)).unwrap_or_default();
+        let apps = parse_entries(&output, &self.0.name);
+        (!apps.is_empty()).then(|| (categories_of(&apps), apps))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ScriptLine {
+    name: String,
+    info: Option<String>,
+    icon: Option<String>,
+    category: Option<String>,
+}
+
+/// Run a script, passing `selection` back to it if given, and return its stdout
+pub fn run(script: &ScriptConfig, selection: Option<&str>) -> Result<String> {
+    // `exec` is a command line; the selection is appended to it as one more argument
+    let mut command = Command::new("sh");
+    command.arg("-c").arg(format!("{} \"$@\"", script.exec)).arg(&script.name);
+
+    match (selection, script.input) {
+        (Some(selection), ScriptInput::Argument) => {
+            command.arg(selection).stdin(Stdio::null());
+        }
+        (Some(_), ScriptInput::Stdin) => {
+            command.stdin(Stdio::piped());
+        }
+        (None, _) => {
+            command.stdin(Stdio::null());
+        }
+    }
+
+    // stderr would draw over the TUI
+    let mut child = command.stdout(Stdio::piped()).stderr(Stdio::null()).spawn()?;
+
+    if let (Some(selection), ScriptInput::Stdin) = (selection, script.input)
+        && let Some(mut stdin) = child.stdin.take()
+    {
+        writeln!(stdin, "{}", selection)?;
+    }
+
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        return Err(eyre!("script '{}' exited with {}", script.name, output.status));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Parse script output into entries. Entries without a category go under `default_category`.
+pub fn parse_entries(output: &str, default_category: &str) -> Vec<AppEntry> {
+    output.lines()
+        .map(str::trim_end)
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let parsed = line.trim_start()
+                .starts_with('{')
+                .then(|| serde_json::from_str::<ScriptLine>(line).ok())
+                .flatten();
+
+            match parsed {
+                Some(item) => AppEntry {
+                    action: Action::Script(item.info.unwrap_or_else(|| item.name.clone())),
+                    name: item.name,
+                    category: item.category.unwrap_or_else(|| default_category.to_string()),
+                    icon: item.icon.filter(|i| !i.is_empty()),
+                    ..Default::default()
+                },
+                None => AppEntry {
+                    name: line.to_string(),
+                    category: default_category.to_string(),
+                    action: Action::Script(line.to_string()),
+                    ..Default::default()
+                },
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn script(exec: &str, input: ScriptInput) -> ScriptConfig {
+        ScriptConfig { name: "test".to_string(), exec: exec.to_string(), input }
+    }
+
+    #[test]
+    fn test_parse_plain_and_json_lines() {
+        let output = "first\n\n{\"name\": \"Second\", \"info\": \"2\", \"icon\": \"*\", \"category\": \"Numbers\"}\n{not json\n";
+        let entries = parse_entries(output, "test");
+        assert_eq!(entries.len(), 3);
+
+        assert_eq!(entries[0].name, "first");
+        assert_eq!(entries[0].category, "test");
+        assert_eq!(entries[0].action, Action::Script("first".to_string()));
+
+        assert_eq!(entries[1].name, "Second");
+        assert_eq!(entries[1].category, "Numbers");
+        assert_eq!(entries[1].icon.as_deref(), Some("*"));
+        assert_eq!(entries[1].action, Action::Script("2".to_string()));
+
+        // Malformed JSON is shown as-is rather than dropped
+        assert_eq!(entries[2].name, "{not json");
+    }
+
+    /// An executable script in the temp directory, removed when dropped
+    struct Stub(std::path::PathBuf);
+
+    impl Stub {
+        fn new(name: &str, body: &str) -> Self {
+            use std::os::unix::fs::PermissionsExt;
+            let path = std::env::temp_dir().join(format!("dstl-{}-{}", name, std::process::id()));
+            std::fs::write(&path, format!("#!/bin/sh\n{}\n", body)).unwrap();
+            std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+            Stub(path)
+        }
+    }
+
+    impl Drop for Stub {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_file(&self.0);
+        }
+    }
+
+    #[test]
+    fn test_selection_is_passed_back() {
+        let stub = Stub::new("script-arg", r#"if [ -n "$1" ]; then echo "got $1"; else echo a; fi"#);
+        let as_arg = script(&stub.0.to_string_lossy(), ScriptInput::Argument);
+        assert_eq!(run(&as_arg, None).unwrap(), "a\n");
+        assert_eq!(run(&as_arg, Some("x y")).unwrap(), "got x y\n");
+
+        // Arguments in `exec` come before the selection
+        let echo = Stub::new("script-echo", r#"echo "$1|$2""#);
+        let with_args = script(&format!("{} --menu", echo.0.display()), ScriptInput::Argument);
+        assert_eq!(run(&with_args, Some("picked")).unwrap(), "--menu|picked\n");
+
+        let on_stdin = script(r#"read line && echo "got $line""#, ScriptInput::Stdin);
+        assert_eq!(run(&on_stdin, Some("z")).unwrap(), "got z\n");
+
+        assert!(run(&script("exit 3", ScriptInput::Argument), None).is_err());
+    }
+}
@@ -0,0 +1,79 @@
+//! Power and session actions: lock, logout, suspend, hibernate, reboot, power off
+
+use nerd_font_symbols::fa;
+use crate::app::{Action, AppEntry};
+use super::{Context, Listing, Source};
+
+/// The session menu (`session`)
+pub struct Session;
+
+impl Source for Session {
+    fn name(&self) -> &str {
+        "session"
+    }
+
+    // Kept in the order below rather than sorted
+    fn load(&self, ctx: &Context) -> Listing {
+        let session = &ctx.config.session;
+        let confirm = |what: &str| session.confirm.then(|| format!("{}?", what));
+
+        let command = |name: &str, icon: &str, exec: &str, confirm: Option<String>| AppEntry {
+            name: name.to_string(),
+            category: "Session".to_string(),
+            exec: exec.to_string(),
+            icon: Some(icon.to_string()),
+            confirm,
+            ..Default::default()
+        };
+
+        let logout = if session.logout.is_empty() {
+            AppEntry {
+                action: Action::Sway("exit".to_string()),
+                ..command("Log Out", fa::FA_RIGHT_FROM_BRACKET, "swaymsg exit", confirm("Log out"))
+            }
+        } else {
+            command("Log Out", fa::FA_RIGHT_FROM_BRACKET, &session.logout, confirm("Log out"))
+        };
+
+        let apps = vec![
+            command("Lock", fa::FA_LOCK, &session.lock, None),
+            logout,
+            command("Suspend", fa::FA_MOON, &session.suspend, None),
+            command("Hibernate", fa::FA_SNOWFLAKE, &session.hibernate, None),
+            command("Reboot", fa::FA_ROTATE_RIGHT, &session.reboot, confirm("Reboot")),
+            command("Power Off", fa::FA_POWER_OFF, &session.poweroff, confirm("Power off")),
+        ];
+
+        (vec!["Session".to_string()], apps)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::test_config;
+
+    #[test]
+    fn test_session_entries() {
+        let mut config = test_config();
+        let ctx = Context { config: &config, previous_window: None };
+        let (_, apps) = Session.load(&ctx);
+
+        let logout = apps.iter().find(|a| a.name == "Log Out").unwrap();
+        assert_eq!(logout.action, Action::Sway("exit".to_string()));
+        assert_eq!(logout.confirm.as_deref(), Some("Log out?"));
+
+        let lock = apps.iter().find(|a| a.name == "Lock").unwrap();
+        assert_eq!(lock.action, Action::Launch);
+        assert_eq!(lock.exec, "loginctl lock-session");
+        assert!(lock.confirm.is_none());
+
+        config.session.logout = "loginctl terminate-session self".to_string();
+        config.session.confirm = false;
+        let ctx = Context { config: &config, previous_window: None };
+        let (_, apps) = Session.load(&ctx);
+        let logout = apps.iter().find(|a| a.name == "Log Out").unwrap();
+        assert_eq!(logout.action, Action::Launch);
+        assert!(apps.iter().all(|a| a.confirm.is_none()));
+    }
+}
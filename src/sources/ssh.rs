@@ -0,0 +1,311 @@
+//! SSH hosts from `~/.ssh/config` (following `Include`) and `~/.ssh/known_hosts`
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use crate::app::AppEntry;
+use crate::launch::shell_quote;
+use super::{Context, Listing, Source};
+
+/// Deepest `Include` nesting followed, in case of include loops
+const MAX_INCLUDE_DEPTH: usize = 16;
+
+/// Known SSH hosts (`ssh`). Launching one runs `ssh <host>` in the configured terminal.
+pub struct SshHosts;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct Host {
+    alias: String,
+    hostname: Option<String>,
+    user: Option<String>,
+    port: Option<String>,
+}
+
+impl Host {
+    fn to_app_entry(&self) -> AppEntry {
+        let target = match (&self.user, &self.hostname) {
+            (Some(user), Some(hostname)) => Some(format!("{}@{}", user, hostname)),
+            (Some(user), None) => Some(format!("{}@", user)),
+            (None, Some(hostname)) if *hostname != self.alias => Some(hostname.clone()),
+            _ => None,
+        };
+
+        let exec = match &self.port {
+            Some(port) => format!("ssh -p {} {}", shell_quote(port), shell_quote(&self.alias)),
+            None => format!("ssh {}", shell_quote(&self.alias)),
+        };
+
+        AppEntry {
+            name: self.alias.clone(),
+            category: "SSH".to_string(),
+            exec,
+            terminal: true,
+            description: target,
+            keywords: self.hostname.iter().chain(self.user.iter()).cloned().collect(),
+            ..Default::default()
+        }
+    }
+}
+
+impl Source for SshHosts {
+    fn name(&self) -> &str {
+        "ssh"
+    }
+
+    fn load(&self, _ctx: &Context) -> Listing {
+        let Some(ssh_dir) = dirs::home_dir().map(|h| h.join(".ssh")) else {
+            return (Vec::new(), Vec::new());
+        };
+
+        let mut hosts = Vec::new();
+        parse_config(&ssh_dir.join("config"), &ssh_dir, 0, &mut hosts);
+
+        let known = fs::read_to_string(ssh_dir.join("known_hosts")).unwrap_or_default();
+        for host in parse_known_hosts(&known) {
+            if !hosts.iter().any(|h: &Host| h.alias == host.alias || h.hostname.as_ref() == Some(&host.alias)) {
+                hosts.push(host);
+            }
+        }
+
+        let mut apps: Vec<AppEntry> = hosts.iter().map(Host::to_app_entry).collect();
+        apps.sort_by_cached_key(|a| a.name.to_lowercase());
+        (vec!["SSH".to_string()], apps)
+    }
+}
+
+fn is_pattern(host: &str) -> bool {
+    host.contains(['*', '?', '!'])
+}
+
+/// Split `Keyword value` or `Keyword=value`, lowercasing the keyword
+fn split_keyword(line: &str) -> Option<(String, &str)> {
+    let line = line.trim();
+    if line.is_empty() || line.starts_with('#') {
+        return None;
+    }
+    let split = line.find(|c: char| c.is_whitespace() || c == '=')?;
+    let (keyword, rest) = line.split_at(split);
+    let value = rest.trim_start_matches(|c: char| c.is_whitespace() || c == '=').trim();
+    Some((keyword.to_lowercase(), value))
+}
+
+/// Collect the concrete hosts from an ssh_config file, following `Include` directives.
+/// Relative includes are resolved against `ssh_dir`, as ssh does for the user config.
+fn parse_config(path: &Path, ssh_dir: &Path, depth: usize, hosts: &mut Vec<Host>) {
+    if depth > MAX_INCLUDE_DEPTH {
+        return;
+    }
+    let Ok(content) = fs::read_to_string(path) else {
+        return;
+    };
+
+    // Hosts declared by the current `Host` line; options apply to all of them
+    let mut block: Vec<usize> = Vec::new();
+
+    for line in content.lines() {
+        let Some((keyword, value)) = split_keyword(line) else {
+            continue;
+        };
+
+        match keyword.as_str() {
+            "host" => {
+                block.clear();
+                for alias in value.split_whitespace().filter(|a| !is_pattern(a)) {
+                    match hosts.iter().position(|h| h.alias == alias) {
+                        Some(existing) => block.push(existing),
+                        None => {
+                            hosts.push(Host { alias: alias.to_string(), ..Default::default() });
+                            block.push(hosts.len() - 1);
+                        }
+                    }
+                }
+            }
+            "match" => block.clear(),
+            // First value wins, as in ssh
+            "hostname" => {
+                for &i in &block {
+                    hosts[i].hostname.get_or_insert_with(|| value.to_string());
+                }
+            }
+            "user" => {
+                for &i in &block {
+                    hosts[i].user.get_or_insert_with(|| value.to_string());
+                }
+            }
+            "port" => {
+                for &i in &block {
+                    hosts[i].port.get_or_insert_with(|| value.to_string());
+                }
+            }
+            "include" => {
+                for pattern in value.split_whitespace() {
+                    let pattern = super::expand_home(pattern);
+                    let pattern = if pattern.is_absolute() { pattern } else { ssh_dir.join(pattern) };
+                    for included in expand_glob(&pattern) {
+                        parse_config(&included, ssh_dir, depth + 1, hosts);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Plain host names from known_hosts. Hashed entries (`|1|...`) can't be turned back into
+/// names and are skipped, as are wildcard and marker (`@revoked`) lines.
+fn parse_known_hosts(content: &str) -> Vec<Host> {
+    let mut hosts: Vec<Host> = Vec::new();
+
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') || line.starts_with('@') {
+            continue;
+        }
+        let Some(names) = line.split_whitespace().next() else {
+            continue;
+        };
+
+        for name in names.split(',') {
+            if name.starts_with('|') || is_pattern(name) {
+                continue;
+            }
+
+            // Non-default ports are written as [host]:port
+            let host = match name.strip_prefix('[').and_then(|rest| rest.split_once("]:")) {
+                Some((host, port)) => Host {
+                    alias: host.to_string(),
+                    port: Some(port.to_string()),
+                    ..Default::default()
+                },
+                None => Host { alias: name.to_string(), ..Default::default() },
+            };
+
+            if !hosts.iter().any(|h| h.alias == host.alias) {
+                hosts.push(host);
+            }
+        }
+    }
+
+    hosts
+}
+
+/// Expand `*` and `?` wildcards in any component of an absolute path, in sorted order
+fn expand_glob(pattern: &Path) -> Vec<PathBuf> {
+    let mut matches = vec![PathBuf::from("/")];
+
+    for component in pattern.components().skip(1) {
+        let component = component.as_os_str().to_string_lossy();
+        if !component.contains(['*', '?']) {
+            matches = matches.into_iter().map(|m| m.join(component.as_ref())).collect();
+            continue;
+        }
+
+        let mut expanded = Vec::new();
+        for dir in &matches {
+            let Ok(entries) = fs::read_dir(dir) else {
+                continue;
+            };
+            let mut names: Vec<String> = entries.flatten()
+                .map(|e| e.file_name().to_string_lossy().into_owned())
+                .filter(|name| !name.starts_with('.') && wildcard_match(&component, name))
+                .collect();
+            names.sort();
+            expanded.extend(names.into_iter().map(|name| dir.join(name)));
+        }
+        matches = expanded;
+    }
+
+    matches.into_iter().filter(|m| m.is_file()).collect()
+}
+
+/// Shell-style match of `*` (any run) and `?` (any one character)
+fn wildcard_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    let (mut p, mut t) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            backtrack = Some((p, t));
+            p += 1;
+        } else if let Some((star, matched)) = backtrack {
+            p = star + 1;
+            t = matched + 1;
+            backtrack = Some((star, matched + 1));
+        } else {
+            return false;
+        }
+    }
+
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_config_with_includes() {
+        let dir = std::env::temp_dir().join(format!("dstl-ssh-test-{}", std::process::id()));
+        fs::create_dir_all(dir.join("config.d")).unwrap();
+        fs::write(dir.join("config"), "\
+Include config.d/*.conf
+Host web db-?
+    HostName example.org
+    User alice
+Host bastion
+    Hostname=10.0.0.1
+    Port 2222
+Host *
+    ServerAliveInterval 60
+").unwrap();
+        fs::write(dir.join("config.d/work.conf"), "Host build\n  User ci\n").unwrap();
+        fs::write(dir.join("config.d/ignored.txt"), "Host nope\n").unwrap();
+
+        let mut hosts = Vec::new();
+        parse_config(&dir.join("config"), &dir, 0, &mut hosts);
+        fs::remove_dir_all(&dir).unwrap();
+
+        let aliases: Vec<&str> = hosts.iter().map(|h| h.alias.as_str()).collect();
+        assert_eq!(aliases, ["build", "web", "bastion"]);
+
+        let web = hosts[1].to_app_entry();
+        assert_eq!(web.exec, "ssh 'web'");
+        assert!(web.terminal);
+        assert_eq!(web.description.as_deref(), Some("alice@example.org"));
+
+        let bastion = hosts[2].to_app_entry();
+        assert_eq!(bastion.exec, "ssh -p '2222' 'bastion'");
+        assert_eq!(bastion.description.as_deref(), Some("10.0.0.1"));
+
+        // Values from the files never reach the shell unquoted
+        let odd = Host { alias: "x;reboot".to_string(), port: Some("22 -oX=y".to_string()), ..Default::default() };
+        assert_eq!(odd.to_app_entry().exec, "ssh -p '22 -oX=y' 'x;reboot'");
+    }
+
+    #[test]
+    fn test_known_hosts() {
+        let hosts = parse_known_hosts("\
+github.com,140.82.121.3 ssh-ed25519 AAAA
+|1|F1E1KeoE/eEWhi10WpGv4OdiO6Y=|3988QV0VE8wmZL7suNrYQLITLCg= ssh-rsa AAAA
+[git.example.org]:2222 ssh-ed25519 AAAA
+*.internal ssh-rsa AAAA
+@revoked bad.example.org ssh-rsa AAAA
+github.com ecdsa-sha2-nistp256 AAAA
+");
+        let aliases: Vec<&str> = hosts.iter().map(|h| h.alias.as_str()).collect();
+        assert_eq!(aliases, ["github.com", "140.82.121.3", "git.example.org"]);
+        assert_eq!(hosts[2].port.as_deref(), Some("2222"));
+    }
+
+    #[test]
+    fn test_wildcard_match() {
+        assert!(wildcard_match("*.conf", "work.conf"));
+        assert!(wildcard_match("db-?", "db-1"));
+        assert!(!wildcard_match("*.conf", "work.txt"));
+        assert!(wildcard_match("a*b*c", "axxbyyc"));
+    }
+}
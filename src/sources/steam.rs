@@ -0,0 +1,280 @@
+//! Installed Steam games, from the library manifests in Valve's KeyValues (VDF) format
+
+use std::fs;
+use std::iter::Peekable;
+use std::path::{Path, PathBuf};
+use std::str::Chars;
+use nerd_font_symbols::fa;
+use crate::app::AppEntry;
+use super::{Context, Listing, Source};
+
+/// `StateFlags` bit set once an app is fully installed
+const FULLY_INSTALLED: u64 = 4;
+
+/// Installed games from every Steam library (`steam`). Launching one runs
+/// `steam steam://rungameid/<id>`.
+pub struct SteamGames;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Game {
+    id: String,
+    name: String,
+    installdir: String,
+}
+
+impl Game {
+    fn to_app_entry(&self) -> AppEntry {
+        AppEntry {
+            name: self.name.clone(),
+            category: "Games".to_string(),
+            exec: format!("steam steam://rungameid/{}", self.id),
+            icon: Some(fa::FA_STEAM.to_string()),
+            description: Some("Steam".to_string()),
+            keywords: vec![self.installdir.clone()],
+            ..Default::default()
+        }
+    }
+}
+
+impl Source for SteamGames {
+    fn name(&self) -> &str {
+        "steam"
+    }
+
+    fn load(&self, ctx: &Context) -> Listing {
+        // ~/.steam/steam is usually a link to one of the other roots
+        let mut libraries: Vec<PathBuf> = Vec::new();
+        for root in &ctx.config.steam_roots {
+            let steamapps = super::expand_home(root).join("steamapps");
+            let Ok(content) = fs::read_to_string(steamapps.join("libraryfolders.vdf")) else {
+                continue;
+            };
+            let found = std::iter::once(steamapps)
+                .chain(library_paths(&parse_vdf(&content)).into_iter().map(|path| path.join("steamapps")));
+            for library in found {
+                let library = library.canonicalize().unwrap_or(library);
+                if !libraries.contains(&library) {
+                    libraries.push(library);
+                }
+            }
+        }
+
+        let mut games: Vec<Game> = Vec::new();
+        for library in &libraries {
+            for game in find_games(library) {
+                if !games.iter().any(|g| g.id == game.id) {
+                    games.push(game);
+                }
+            }
+        }
+
+        let mut apps: Vec<AppEntry> = games.iter().map(Game::to_app_entry).collect();
+        apps.sort_by_cached_key(|a| a.name.to_lowercase());
+        (vec!["Games".to_string()], apps)
+    }
+}
+
+/// Library directories from `libraryfolders.vdf`: blocks with a `path` in the current format,
+/// plain numbered paths in the old one
+fn library_paths(vdf: &KeyValues) -> Vec<PathBuf> {
+    let Some(folders) = vdf.block("libraryfolders") else {
+        return Vec::new();
+    };
+    folders.0.iter()
+        .filter(|(key, _)| key.chars().all(|c| c.is_ascii_digit()))
+        .filter_map(|(_, value)| match value {
+            Value::Text(path) => Some(PathBuf::from(path)),
+            Value::Block(folder) => folder.text("path").map(PathBuf::from),
+        })
+        .collect()
+}
+
+/// Fully installed games from the `appmanifest_*.acf` files in a `steamapps` directory
+fn find_games(steamapps: &Path) -> Vec<Game> {
+    let Ok(entries) = fs::read_dir(steamapps) else {
+        return Vec::new();
+    };
+
+    let mut games = Vec::new();
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().into_owned();
+        if !(name.starts_with("appmanifest_") && name.ends_with(".acf")) {
+            continue;
+        }
+        let Ok(content) = fs::read_to_string(entry.path()) else {
+            continue;
+        };
+        if let Some(game) = parse_manifest(&parse_vdf(&content)) {
+            games.push(game);
+        }
+    }
+    games
+}
+
+fn parse_manifest(vdf: &KeyValues) -> Option<Game> {
+    let state = vdf.block("AppState")?;
+    let flags: u64 = state.text("StateFlags")?.parse().ok()?;
+    if flags & FULLY_INSTALLED == 0 {
+        return None;
+    }
+
+    let game = Game {
+        id: state.text("appid")?.to_string(),
+        name: state.text("name")?.to_string(),
+        installdir: state.text("installdir").unwrap_or_default().to_string(),
+    };
+    (!is_tool(&game)).then_some(game)
+}
+
+/// Compatibility tools and runtimes Steam installs alongside games
+fn is_tool(game: &Game) -> bool {
+    game.id == "228980"
+        || game.name.starts_with("Proton ")
+        || game.name.starts_with("Steam Linux Runtime")
+        || game.name.starts_with("Steamworks ")
+}
+
+/// A KeyValues block: keys in file order, each with a string or a nested block
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct KeyValues(Vec<(String, Value)>);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Value {
+    Text(String),
+    Block(KeyValues),
+}
+
+impl KeyValues {
+    /// Keys are case-insensitive, as in Steam (`AppState` is sometimes `appstate`)
+    fn get(&self, key: &str) -> Option<&Value> {
+        self.0.iter().find(|(k, _)| k.eq_ignore_ascii_case(key)).map(|(_, value)| value)
+    }
+
+    fn text(&self, key: &str) -> Option<&str> {
+        match self.get(key)? {
+            Value::Text(text) => Some(text),
+            Value::Block(_) => None,
+        }
+    }
+
+    fn block(&self, key: &str) -> Option<&KeyValues> {
+        match self.get(key)? {
+            Value::Block(block) => Some(block),
+            Value::Text(_) => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Open,
+    Close,
+    Str(String),
+}
+
+/// Parse KeyValues text. Whatever can't be parsed ends the block it's in rather than failing,
+/// so a truncated file still yields what came before.
+fn parse_vdf(content: &str) -> KeyValues {
+    let mut tokens = Tokens(content.chars().peekable());
+    parse_block(&mut tokens)
+}
+
+fn parse_block(tokens: &mut Tokens) -> KeyValues {
+    let mut block = KeyValues::default();
+    while let Some(Token::Str(key)) = tokens.next() {
+        match tokens.next() {
+            Some(Token::Str(text)) => block.0.push((key, Value::Text(text))),
+            Some(Token::Open) => block.0.push((key, Value::Block(parse_block(tokens)))),
+            _ => break,
+        }
+    }
+    block
+}
+
+struct Tokens<'a>(Peekable<Chars<'a>>);
+
+impl Iterator for Tokens<'_> {
+    type Item = Token;
+
+    fn next(&mut self) -> Option<Token> {
+        loop {
+            let c = self.0.next()?;
+            match c {
+                '{' => return Some(Token::Open),
+                '}' => return Some(Token::Close),
+                '"' => {
+                    let mut text = String::new();
+                    while let Some(c) = self.0.next() {
+                        match c {
+                            '"' => break,
+                            '\\' => match self.0.next() {
+                                Some('n') => text.push('\n'),
+                                Some('t') => text.push('\t'),
+                                Some(c) => text.push(c),
+                                None => break,
+                            },
+                            c => text.push(c),
+                        }
+                    }
+                    return Some(Token::Str(text));
+                }
+                '/' if self.0.peek() == Some(&'/') => {
+                    self.0.by_ref().find(|&c| c == '\n');
+                }
+                c if c.is_whitespace() => {}
+                c => {
+                    let mut text = c.to_string();
+                    while let Some(&c) = self.0.peek() {
+                        if c.is_whitespace() || matches!(c, '{' | '}' | '"') {
+                            break;
+                        }
+                        text.push(c);
+                        self.0.next();
+                    }
+                    // Platform conditionals like [$WIN32] after a value are ignored
+                    if !text.starts_with('[') {
+                        return Some(Token::Str(text));
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixtures() -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/steam/steamapps")
+    }
+
+    #[test]
+    fn test_library_paths() {
+        let content = fs::read_to_string(fixtures().join("libraryfolders.vdf")).unwrap();
+        let vdf = parse_vdf(&content);
+        assert_eq!(library_paths(&vdf), [
+            PathBuf::from("/home/user/.local/share/Steam"),
+            PathBuf::from("/mnt/games/SteamLibrary"),
+        ]);
+        let label = vdf.block("libraryfolders").and_then(|f| f.block("1")).and_then(|f| f.text("label"));
+        assert_eq!(label, Some("Games \"SSD\""));
+
+        let old = parse_vdf("\"LibraryFolders\"\n{\n\t\"TimeNextStatsReport\"\t\"1729245541\"\n\t\"1\"\t\"/mnt/games\"\n}\n");
+        assert_eq!(library_paths(&old), [PathBuf::from("/mnt/games")]);
+    }
+
+    #[test]
+    fn test_find_games() {
+        let mut games = find_games(&fixtures());
+        games.sort_by(|a, b| a.name.cmp(&b.name));
+        // Proton, the redistributables and the half-downloaded Witcher are left out
+        let names: Vec<&str> = games.iter().map(|g| g.name.as_str()).collect();
+        assert_eq!(names, ["Dota 2", "Hades"]);
+
+        let dota = games[0].to_app_entry();
+        assert_eq!(dota.exec, "steam steam://rungameid/570");
+        assert_eq!(dota.category, "Games");
+        assert_eq!(dota.keywords, ["dota 2 beta"]);
+    }
+}
@@ -0,0 +1,248 @@
+//! Sources listing sway state: open windows, workspaces and the scratchpad
+
+use crate::app::{Action, AppEntry};
+use crate::sway::{Window, Workspace, quote};
+use super::{Context, Listing, Source};
+
+/// Switch to (or create) a sway workspace
+fn switch_to_workspace(name: &str) -> Action {
+    Action::Sway(format!("workspace {}", quote(name)))
+}
+
+/// Move a sway window to a workspace
+fn move_to_workspace(window: i64, name: &str) -> Action {
+    Action::Sway(format!("[con_id={}] move container to workspace {}", window, quote(name)))
+}
+
+/// Open windows, using workspaces as categories (`windows`)
+pub struct Windows;
+
+impl Source for Windows {
+    fn name(&self) -> &str {
+        "windows"
+    }
+
+    // Windows stay in most-recently-focused order
+    fn load(&self, _ctx: &Context) -> Listing {
+        let windows = crate::sway::Client::connect()
+            .and_then(|mut client| client.get_windows())
+            .unwrap_or_default();
+        window_entries(windows)
+    }
+}
+
+fn window_entries(windows: Vec<Window>) -> Listing {
+    let mut categories: Vec<String> = Vec::new();
+    let mut apps = Vec::new();
+
+    for window in windows {
+        let workspace = if window.scratchpad {
+            "Scratchpad".to_string()
+        } else {
+            window.workspace.clone()
+        };
+        if !categories.contains(&workspace) {
+            categories.push(workspace.clone());
+        }
+
+        let mut tags = vec![workspace.clone()];
+        if window.floating && !window.scratchpad {
+            tags.push("floating".to_string());
+        }
+
+        apps.push(AppEntry {
+            name: format!("{} — {}  [{}]", window.app, window.title, tags.join(", ")),
+            category: workspace,
+            action: Action::Sway(format!("[con_id={}] focus", window.id)),
+            ..Default::default()
+        });
+    }
+
+    (categories, apps)
+}
+
+/// Workspaces, using outputs as categories (`workspaces`). The alternate action moves the
+/// window focused before dstl opened to the chosen workspace.
+pub struct Workspaces;
+
+impl Source for Workspaces {
+    fn name(&self) -> &str {
+        "workspaces"
+    }
+
+    fn uses_previous_window(&self) -> bool {
+        true
+    }
+
+    // Workspaces stay in sway's order
+    fn load(&self, ctx: &Context) -> Listing {
+        let Ok(mut client) = crate::sway::Client::connect() else {
+            return (Vec::new(), Vec::new());
+        };
+        let workspaces = client.get_workspaces().unwrap_or_default();
+        let windows = client.get_windows().unwrap_or_default();
+        workspace_entries(workspaces, &windows, ctx.previous_window)
+    }
+
+    /// Offer to create a workspace when the query doesn't name an existing one
+    fn offers(&self, query: &str, apps: &[AppEntry], ctx: &Context) -> Vec<AppEntry> {
+        let name = query.trim();
+        if name.is_empty() {
+            return Vec::new();
+        }
+
+        let action = switch_to_workspace(name);
+        if apps.iter().any(|a| a.action == action) {
+            return Vec::new();
+        }
+
+        vec![AppEntry {
+            name: format!("Create workspace \"{}\"", name),
+            category: "New".to_string(),
+            action,
+            alt_action: ctx.previous_window.map(|id| move_to_workspace(id, name)),
+            ..Default::default()
+        }]
+    }
+}
+
+/// Workspaces with their window counts from `windows`; the alternate action moves
+/// `previous_window` there
+fn workspace_entries(workspaces: Vec<Workspace>, windows: &[Window], previous_window: Option<i64>) -> Listing {
+    let mut categories: Vec<String> = Vec::new();
+    let mut apps = Vec::new();
+
+    for workspace in workspaces {
+        if !categories.contains(&workspace.output) {
+            categories.push(workspace.output.clone());
+        }
+
+        let count = windows.iter().filter(|w| w.workspace == workspace.name).count();
+        let mut tags = vec![workspace.output.clone()];
+        if workspace.focused {
+            tags.push("focused".to_string());
+        } else if workspace.visible {
+            tags.push("visible".to_string());
+        }
+        if workspace.urgent {
+            tags.push("urgent".to_string());
+        }
+        tags.push(if count == 1 { "1 window".to_string() } else { format!("{} windows", count) });
+
+        apps.push(AppEntry {
+            name: format!("{}  [{}]", workspace.name, tags.join(", ")),
+            category: workspace.output,
+            action: switch_to_workspace(&workspace.name),
+            alt_action: previous_window.map(|id| move_to_workspace(id, &workspace.name)),
+            ..Default::default()
+        });
+    }
+
+    (categories, apps)
+}
+
+/// Windows hidden in the scratchpad (`scratchpad`)
+pub struct Scratchpad;
+
+impl Source for Scratchpad {
+    fn name(&self) -> &str {
+        "scratchpad"
+    }
+
+    fn load(&self, _ctx: &Context) -> Listing {
+        let windows = crate::sway::Client::connect()
+            .and_then(|mut client| client.get_tree())
+            .map(|tree| tree.scratchpad_windows())
+            .unwrap_or_default();
+        (vec!["Scratchpad".to_string()], windows.iter().map(scratchpad_entry).collect())
+    }
+}
+
+fn scratchpad_entry(window: &Window) -> AppEntry {
+    AppEntry {
+        name: format!("{} — {}", window.app, window.title),
+        category: "Scratchpad".to_string(),
+        action: Action::Sway(format!("[con_id={}] scratchpad show", window.id)),
+        // Hidden scratchpad windows must be shown before they can stop floating
+        alt_action: Some(Action::Sway(format!(
+            "[con_id={id}] scratchpad show; [con_id={id}] floating disable",
+            id = window.id
+        ))),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::{App, Mode};
+    use crate::config::test_config;
+
+    fn window(id: i64, app: &str, workspace: &str) -> Window {
+        Window {
+            id,
+            app: app.to_string(),
+            title: format!("{} window", app),
+            workspace: workspace.to_string(),
+            floating: false,
+            scratchpad: false,
+            pid: None,
+        }
+    }
+
+    fn workspace(name: &str, output: &str, focused: bool) -> Workspace {
+        Workspace { name: name.to_string(), focused, visible: focused, urgent: false, output: output.to_string() }
+    }
+
+    #[test]
+    fn test_window_entries() {
+        let floating = Window { floating: true, ..window(2, "mpv", "2") };
+        let hidden = Window { scratchpad: true, floating: true, ..window(3, "keepassxc", "__i3_scratch") };
+        let (categories, apps) = window_entries(vec![window(1, "foot", "main"), floating, hidden]);
+
+        assert_eq!(categories, ["main", "2", "Scratchpad"]);
+        let names: Vec<&str> = apps.iter().map(|a| a.name.as_str()).collect();
+        assert_eq!(names, [
+            "foot — foot window  [main]",
+            "mpv — mpv window  [2, floating]",
+            "keepassxc — keepassxc window  [Scratchpad]",
+        ]);
+        assert_eq!(apps[1].action, Action::Sway("[con_id=2] focus".to_string()));
+    }
+
+    #[test]
+    fn test_workspace_entries() {
+        let workspaces = vec![workspace("main", "DP-1", true), workspace("2", "HDMI-A-1", false)];
+        let windows = [window(1, "foot", "main"), window(2, "mpv", "main")];
+        let (categories, apps) = workspace_entries(workspaces, &windows, Some(7));
+
+        assert_eq!(categories, ["DP-1", "HDMI-A-1"]);
+        assert_eq!(apps[0].name, "main  [DP-1, focused, 2 windows]");
+        assert_eq!(apps[1].name, "2  [HDMI-A-1, 0 windows]");
+        assert_eq!(apps[0].action, Action::Sway("workspace \"main\"".to_string()));
+        assert_eq!(apps[1].alt_action, Some(Action::Sway("[con_id=7] move container to workspace \"2\"".to_string())));
+
+        let config = test_config();
+        let ctx = Context { config: &config, previous_window: Some(7) };
+        assert!(Workspaces.offers("main", &apps, &ctx).is_empty());
+        let create = Workspaces.offers("mai", &apps, &ctx);
+        assert_eq!(create[0].name, "Create workspace \"mai\"");
+        assert_eq!(create[0].alt_action, Some(Action::Sway("[con_id=7] move container to workspace \"mai\"".to_string())));
+
+        // The workspace the query matches comes before the offer to create one
+        let mut app = App::new("workspaces", Mode::SinglePane, &config);
+        app.set_entries(categories, apps);
+        app.input = "mai".into();
+        app.refresh_pinned();
+        assert_eq!(app.selected_entry().map(|e| &e.action), Some(&Action::Sway("workspace \"main\"".to_string())));
+        assert_eq!(app.row_names().last().map(String::as_str), Some("Create workspace \"mai\""));
+    }
+
+    #[test]
+    fn test_scratchpad_entry() {
+        let entry = scratchpad_entry(&Window { scratchpad: true, ..window(4, "keepassxc", "__i3_scratch") });
+        assert_eq!(entry.name, "keepassxc — keepassxc window");
+        assert_eq!(entry.action, Action::Sway("[con_id=4] scratchpad show".to_string()));
+        assert_eq!(entry.alt_action, Some(Action::Sway("[con_id=4] scratchpad show; [con_id=4] floating disable".to_string())));
+    }
+}
@@ -0,0 +1,157 @@
+//! tmux sessions, listed with `tmux list-sessions`
+
+use std::process::{Command, Stdio};
+use nerd_font_symbols::fa;
+use crate::app::AppEntry;
+use crate::launch::shell_quote;
+use super::{Context, Listing, Source};
+
+/// Fields asked of `list-sessions`; the name goes last since it is the only free-form one
+const FORMAT: &str = "#{session_attached}\t#{session_windows}\t#{session_activity}\t#{session_name}";
+
+/// Running tmux sessions, most recently active first (`tmux`). Enter attaches to the session
+/// in the configured terminal; a name that isn't taken offers to create that session.
+pub struct TmuxSessions;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Session {
+    name: String,
+    /// Number of clients attached
+    attached: u32,
+    windows: u32,
+    /// Unix time of the last activity
+    activity: u64,
+}
+
+impl Session {
+    fn to_app_entry(&self, tmux: &str, now: u64) -> AppEntry {
+        let mut details = vec![if self.windows == 1 { "1 window".to_string() } else { format!("{} windows", self.windows) }];
+        if self.attached > 0 {
+            details.push("attached".to_string());
+        }
+        details.push(format!("active {}", ago(now.saturating_sub(self.activity))));
+
+        AppEntry {
+            name: self.name.clone(),
+            category: "tmux".to_string(),
+            exec: format!("{} attach -t {}", tmux, shell_quote(&format!("={}", self.name))),
+            terminal: true,
+            icon: Some(fa::FA_TERMINAL.to_string()),
+            description: Some(details.join(", ")),
+            ..Default::default()
+        }
+    }
+}
+
+impl Source for TmuxSessions {
+    fn name(&self) -> &str {
+        "tmux"
+    }
+
+    fn load(&self, ctx: &Context) -> Listing {
+        let tmux = &ctx.config.tmux_command;
+        let mut args = tmux.split_whitespace();
+        let Some(program) = args.next() else {
+            return (Vec::new(), Vec::new());
+        };
+
+        // Without a server there are no sessions, and tmux says so on stderr
+        let output = Command::new(program)
+            .args(args)
+            .args(["list-sessions", "-F", FORMAT])
+            .stdin(Stdio::null())
+            .stderr(Stdio::null())
+            .output();
+        let mut sessions = match output {
+            Ok(output) if output.status.success() => parse_sessions(&String::from_utf8_lossy(&output.stdout)),
+            _ => Vec::new(),
+        };
+        sessions.sort_by_key(|s| std::cmp::Reverse(s.activity));
+
+        let now = crate::frecency::now();
+        let apps = sessions.iter().map(|s| s.to_app_entry(tmux, now)).collect();
+        (vec!["tmux".to_string()], apps)
+    }
+
+    /// Offer to create a session when the query doesn't name an existing one
+    fn offers(&self, query: &str, apps: &[AppEntry], ctx: &Context) -> Vec<AppEntry> {
+        let name = query.trim();
+        // Inside combi, `apps` holds other sources' entries too
+        if name.is_empty() || apps.iter().any(|a| a.category == "tmux" && a.name == name) {
+            return Vec::new();
+        }
+
+        vec![AppEntry {
+            name: format!("Create session \"{}\"", name),
+            category: "New".to_string(),
+            exec: format!("{} new-session -s {}", ctx.config.tmux_command, shell_quote(name)),
+            terminal: true,
+            icon: Some(fa::FA_PLUS.to_string()),
+            ..Default::default()
+        }]
+    }
+}
+
+/// Parse `list-sessions` output in `FORMAT`
+fn parse_sessions(output: &str) -> Vec<Session> {
+    output.lines()
+        .filter_map(|line| {
+            let mut fields = line.splitn(4, '\t');
+            let attached = fields.next()?.parse().ok()?;
+            let windows = fields.next()?.parse().ok()?;
+            let activity = fields.next()?.parse().ok()?;
+            let name = fields.next()?.to_string();
+            Some(Session { name, attached, windows, activity })
+        })
+        .collect()
+}
+
+/// A duration in seconds as `just now`, `5 minutes ago`, `2 hours ago` or `3 days ago`
+fn ago(seconds: u64) -> String {
+    let (count, unit) = match seconds {
+        0..60 => return "just now".to_string(),
+        60..3600 => (seconds / 60, "minute"),
+        3600..86400 => (seconds / 3600, "hour"),
+        _ => (seconds / 86400, "day"),
+    };
+    format!("{} {}{} ago", count, unit, if count == 1 { "" } else { "s" })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::os::unix::fs::PermissionsExt;
+    use crate::config::test_config;
+
+    #[test]
+    fn test_load_sessions() {
+        // A stub tmux that checks its arguments and prints two sessions
+        let now = crate::frecency::now();
+        let stub = std::env::temp_dir().join(format!("dstl-tmux-stub-{}", std::process::id()));
+        fs::write(&stub, format!(
+            "#!/bin/sh\n[ \"$1 $2 $3\" = '-L test list-sessions' ] || exit 1\nprintf '0\\t1\\t{}\\tnotes\\n1\\t3\\t{}\\twork: dstl\\n'\n",
+            now - 7200,
+            now - 30,
+        )).unwrap();
+        fs::set_permissions(&stub, fs::Permissions::from_mode(0o755)).unwrap();
+
+        let mut config = test_config();
+        config.tmux_command = format!("{} -L test", stub.display());
+        let ctx = Context { config: &config, previous_window: None };
+        let (_, apps) = TmuxSessions.load(&ctx);
+        fs::remove_file(&stub).unwrap();
+
+        let names: Vec<&str> = apps.iter().map(|a| a.name.as_str()).collect();
+        assert_eq!(names, ["work: dstl", "notes"]);
+        assert_eq!(apps[0].description.as_deref(), Some("3 windows, attached, active just now"));
+        assert_eq!(apps[1].description.as_deref(), Some("1 window, active 2 hours ago"));
+        assert_eq!(apps[0].exec, format!("{} -L test attach -t '=work: dstl'", stub.display()));
+        assert!(apps[0].terminal);
+
+        let create = TmuxSessions.offers("scratch", &apps, &ctx);
+        assert_eq!(create[0].name, "Create session \"scratch\"");
+        assert_eq!(create[0].exec, format!("{} -L test new-session -s 'scratch'", stub.display()));
+        assert!(TmuxSessions.offers("notes", &apps, &ctx).is_empty());
+    }
+}
@@ -10,6 +10,7 @@ const MAGIC: &[u8] = b"i3-ipc";
 #[derive(Copy, Clone)]
 enum MessageType {
     Command = 0,
+    GetWorkspaces = 1,
     GetTree = 4,
 }
 
@@ -18,14 +19,113 @@ pub struct Client {
 }
 
 #[derive(Debug, Deserialize)]
-#[allow(dead_code)]
 pub struct Node {
-    id: i64,
-    name: Option<String>,
-    focused: bool,
-    fullscreen_mode: Option<u8>,
-    nodes: Vec<Node>,
-    floating_nodes: Vec<Node>,
+    pub id: i64,
+    pub name: Option<String>,
+    #[serde(rename = "type")]
+    pub node_type: String,
+    pub focused: bool,
+    pub fullscreen_mode: Option<u8>,
+    /// Wayland-native windows only
+    pub app_id: Option<String>,
+    /// Xwayland windows only
+    pub window_properties: Option<WindowProperties>,
+    pub pid: Option<i32>,
+    /// Child ids, most recently focused first
+    #[serde(default)]
+    pub focus: Vec<i64>,
+    #[serde(default)]
+    pub nodes: Vec<Node>,
+    #[serde(default)]
+    pub floating_nodes: Vec<Node>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct WindowProperties {
+    pub class: Option<String>,
+    pub title: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Workspace {
+    pub name: String,
+    pub focused: bool,
+    pub visible: bool,
+    pub urgent: bool,
+    pub output: String,
+}
+
+/// A window flattened out of the tree, with the context needed to list it
+#[derive(Debug, Clone)]
+pub struct Window {
+    pub id: i64,
+    /// app_id for Wayland windows, X11 class for Xwayland ones
+    pub app: String,
+    pub title: String,
+    pub workspace: String,
+    pub floating: bool,
+    pub scratchpad: bool,
+    pub pid: Option<i32>,
+}
+
+/// Name of the hidden workspace sway keeps scratchpad windows on
+pub const SCRATCH_WORKSPACE: &str = "__i3_scratch";
+
+impl Node {
+    fn is_window(&self) -> bool {
+        matches!(self.node_type.as_str(), "con" | "floating_con")
+            && self.nodes.is_empty()
+            && self.floating_nodes.is_empty()
+            && (self.pid.is_some() || self.app_id.is_some() || self.window_properties.is_some())
+    }
+
+    /// Children (tiling and floating), most recently focused first
+    fn children_by_focus(&self) -> Vec<&Node> {
+        let mut children: Vec<&Node> = self.nodes.iter().chain(self.floating_nodes.iter()).collect();
+        children.sort_by_key(|c| self.focus.iter().position(|&id| id == c.id).unwrap_or(usize::MAX));
+        children
+    }
+
+    /// Every window in the tree, most recently focused first
+    pub fn windows(&self) -> Vec<Window> {
+        let mut windows = Vec::new();
+        self.collect_windows(None, false, &mut windows);
+        windows
+    }
+
+    /// Windows currently hidden in the scratchpad, most recently focused first
+    pub fn scratchpad_windows(&self) -> Vec<Window> {
+        self.windows().into_iter().filter(|w| w.scratchpad).collect()
+    }
+
+    fn collect_windows(&self, workspace: Option<&str>, floating: bool, out: &mut Vec<Window>) {
+        let workspace = if self.node_type == "workspace" { self.name.as_deref() } else { workspace };
+
+        if self.is_window() {
+            let props = self.window_properties.as_ref();
+            let app = self.app_id.clone()
+                .or_else(|| props.and_then(|p| p.class.clone()))
+                .unwrap_or_default();
+            let title = self.name.clone()
+                .or_else(|| props.and_then(|p| p.title.clone()))
+                .unwrap_or_default();
+            out.push(Window {
+                id: self.id,
+                app,
+                title,
+                workspace: workspace.unwrap_or_default().to_string(),
+                floating: floating || self.node_type == "floating_con",
+                scratchpad: workspace == Some(SCRATCH_WORKSPACE),
+                pid: self.pid,
+            });
+            return;
+        }
+
+        for child in self.children_by_focus() {
+            let child_floating = floating || self.floating_nodes.iter().any(|f| f.id == child.id);
+            child.collect_windows(workspace, child_floating, out);
+        }
+    }
 }
 
 impl Client {
@@ -78,13 +178,38 @@ impl Client {
     }
 
     pub fn exec(&mut self, cmd: &str) -> Result<()> {
-        let payload = format!("exec {}", cmd);
-        self.send_message(MessageType::Command, &payload)?;
-        let _resp = self.receive_message()?;
         // We could check "success" in response but typically exec succeeds in queueing
+        self.run_command(&format!("exec {}", cmd))
+    }
+
+    /// Run an arbitrary sway command, e.g. `[con_id=4] focus`
+    pub fn run_command(&mut self, cmd: &str) -> Result<()> {
+        self.send_message(MessageType::Command, cmd)?;
+        let _resp = self.receive_message()?;
         Ok(())
     }
 
+    pub fn get_workspaces(&mut self) -> Result<Vec<Workspace>> {
+        self.send_message(MessageType::GetWorkspaces, "")?;
+        let resp = self.receive_message()?;
+        Ok(serde_json::from_str(&resp)?)
+    }
+
+    /// The window that had focus before dstl's own window took it
+    pub fn get_previous_window(&mut self) -> Result<Option<Window>> {
+        Ok(self.get_windows()?.into_iter().find(|w| !w.scratchpad))
+    }
+
+    /// All windows except the one dstl itself is running in, most recently focused first
+    pub fn get_windows(&mut self) -> Result<Vec<Window>> {
+        let tree = self.get_tree()?;
+        let own_pids = ancestor_pids();
+        Ok(tree.windows()
+            .into_iter()
+            .filter(|w| w.pid.is_none_or(|pid| !own_pids.contains(&pid)))
+            .collect())
+    }
+
     pub fn set_fullscreen(&mut self, enable: bool, node_id: Option<i64>) -> Result<()> {
         let cmd = match (enable, node_id) {
             (true, Some(id)) => format!("[con_id={}] fullscreen enable", id),
@@ -131,3 +256,87 @@ fn find_focused_node(node: &Node) -> Option<&Node> {
 
     None
 }
+
+/// Quote an argument for a sway command, e.g. a workspace name containing spaces
+pub fn quote(arg: &str) -> String {
+    format!("\"{}\"", arg.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// PIDs of this process and its ancestors, used to recognise the terminal window dstl runs in
+fn ancestor_pids() -> Vec<i32> {
+    let mut pids = Vec::new();
+    let mut pid = std::process::id() as i32;
+
+    while pid > 1 && pids.len() < 64 {
+        pids.push(pid);
+        let Ok(stat) = std::fs::read_to_string(format!("/proc/{}/stat", pid)) else {
+            break;
+        };
+        // Fields after the parenthesised command name: state, ppid, ...
+        let ppid = stat.rsplit_once(')')
+            .and_then(|(_, rest)| rest.split_whitespace().nth(1))
+            .and_then(|p| p.parse().ok());
+        match ppid {
+            Some(ppid) => pid = ppid,
+            None => break,
+        }
+    }
+
+    pids
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TREE: &str = r#"{
+        "id": 1, "name": "root", "type": "root", "focused": false, "focus": [3, 2],
+        "nodes": [
+            {"id": 2, "name": "__i3", "type": "output", "focused": false, "focus": [20], "nodes": [
+                {"id": 20, "name": "__i3_scratch", "type": "workspace", "focused": false, "nodes": [],
+                 "floating_nodes": [
+                    {"id": 21, "name": "notes", "type": "floating_con", "focused": false, "app_id": "foot", "pid": 400, "nodes": [], "floating_nodes": []}
+                 ]}
+            ]},
+            {"id": 3, "name": "DP-1", "type": "output", "focused": false, "focus": [30], "nodes": [
+                {"id": 30, "name": "1", "type": "workspace", "focused": false, "focus": [32, 31], "nodes": [
+                    {"id": 31, "name": "Mozilla Firefox", "type": "con", "focused": false, "app_id": "firefox", "pid": 100, "nodes": [], "floating_nodes": []}
+                 ],
+                 "floating_nodes": [
+                    {"id": 32, "name": "Steam", "type": "floating_con", "focused": true, "app_id": null, "pid": 200,
+                     "window_properties": {"class": "steam", "instance": "steam", "title": "Steam"}, "nodes": [], "floating_nodes": []}
+                 ]}
+            ]}
+        ]
+    }"#;
+
+    #[test]
+    fn test_windows_in_focus_order() {
+        let tree: Node = serde_json::from_str(TREE).unwrap();
+        let windows = tree.windows();
+        let ids: Vec<i64> = windows.iter().map(|w| w.id).collect();
+        assert_eq!(ids, vec![32, 31, 21]);
+
+        let steam = &windows[0];
+        assert_eq!(steam.app, "steam");
+        assert_eq!(steam.workspace, "1");
+        assert!(steam.floating && !steam.scratchpad);
+
+        let firefox = &windows[1];
+        assert_eq!(firefox.app, "firefox");
+        assert_eq!(firefox.title, "Mozilla Firefox");
+        assert!(!firefox.floating);
+
+        let notes = &windows[2];
+        assert!(notes.scratchpad && notes.floating);
+
+        let scratchpad: Vec<i64> = tree.scratchpad_windows().iter().map(|w| w.id).collect();
+        assert_eq!(scratchpad, vec![21]);
+    }
+
+    #[test]
+    fn test_quote() {
+        assert_eq!(quote("1: web"), r#""1: web""#);
+        assert_eq!(quote(r#"say "hi""#), r#""say \"hi\"""#);
+    }
+}
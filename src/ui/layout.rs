@@ -1,7 +1,7 @@
 use ratatui::{
     Frame,
-    layout::{Layout, Constraint, Direction, Rect},
-    widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
+    layout::{Alignment, Layout, Constraint, Direction, Rect},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph, Wrap},
     style::{Style, Color},
 };
 use tui_input::Input;
@@ -132,7 +132,7 @@ pub fn render_list(
 
     let highlight_style = match config.colors.highlight_type.to_lowercase().as_str() {
         "foreground" => Style::default().fg(selection_color),
-        "background" | _ => Style::default().bg(selection_color).fg(Color::Black),
+        _ => Style::default().bg(selection_color).fg(Color::Black),
     };
     
     let list = List::new(list_items)
@@ -142,3 +142,53 @@ pub fn render_list(
     
     f.render_stateful_widget(list, area, &mut state);
 }
+
+/// Modal yes/no prompt drawn centered over whatever is on screen
+pub fn render_confirm_dialog(f: &mut Frame, question: &str, config: &DstlConfig) {
+    let full_area = f.area();
+    let width = (question.chars().count() as u16 + 8).max(30).min(full_area.width);
+    let height = 5.min(full_area.height);
+    let area = Rect {
+        x: full_area.x + (full_area.width.saturating_sub(width)) / 2,
+        y: full_area.y + (full_area.height.saturating_sub(height)) / 2,
+        width,
+        height,
+    };
+
+    let focus_color = LauncherTheme::parse_color(&config.colors.focus);
+
+    let block = Block::default()
+        .title(" Confirm ")
+        .borders(Borders::ALL)
+        .border_type(LauncherTheme::parse_border_type(&config.colors.border_style))
+        .border_style(Style::default().fg(focus_color));
+
+    let paragraph = Paragraph::new(format!("{}\n\n[y]es / [n]o", question))
+        .block(block)
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true })
+        .style(Style::default().fg(focus_color));
+
+    f.render_widget(Clear, area);
+    f.render_widget(paragraph, area);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::{Terminal, backend::TestBackend};
+    use crate::config::test_config;
+
+    #[test]
+    fn test_confirm_dialog_fits_narrow_terminal() {
+        let config = test_config();
+        let mut terminal = Terminal::new(TestBackend::new(1, 10)).unwrap();
+        terminal.draw(|f| render_confirm_dialog(f, "Power off?", &config)).unwrap();
+
+        // The dialog shrinks to the terminal's width rather than its usual minimum of 30
+        let mut terminal = Terminal::new(TestBackend::new(20, 10)).unwrap();
+        terminal.draw(|f| render_confirm_dialog(f, "Power off?", &config)).unwrap();
+        let top: String = (0..20).map(|x| terminal.backend().buffer()[(x, 2)].symbol()).collect();
+        assert_eq!(top, "┌ Confirm ─────────┐");
+    }
+}
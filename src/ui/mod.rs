@@ -20,4 +20,8 @@ pub fn draw(f: &mut Frame, app: &mut App, search_position: SearchPosition, confi
         }
         Mode::DualPane => dual_pane::draw(f, app, search_position, config),
     }
+
+    if let Some(entry) = &app.confirming {
+        layout::render_confirm_dialog(f, entry.confirm.as_deref().unwrap_or_default(), config);
+    }
 }
@@ -13,16 +13,14 @@ pub fn draw(
 ) {
     let chunks = layout::vertical_split(f, 3, search_position);
     
-    let filtered_apps: Vec<String> = app
-        .visible_apps()
-        .into_iter()
-        .map(|a| a.name.clone())
-        .collect();
+    let filtered_apps = app.row_names();
+    
+    let title = if app.is_did_you_mean() { " Did you mean? " } else { " Apps " };
     
     layout::render_list(
         f,
         chunks.1,
-        " Apps ",
+        title,
         &filtered_apps,
         selected,
         focus == Focus::Apps,
@@ -0,0 +1,126 @@
+//! Web shortcuts for the query row: URLs to open directly and bangs like `w tokio` that
+//! expand into a search engine URL.
+
+use crate::config::SearchEngine;
+
+/// What the query asks to open
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Shortcut {
+    /// The query itself is an address; a missing scheme is filled in as https
+    Url(String),
+    /// A bang search: the engine's name and the expanded URL
+    Search { engine: String, terms: String, url: String },
+}
+
+/// Interpret a query as a URL or a bang search, if it is one
+pub fn parse(query: &str, engines: &[SearchEngine]) -> Option<Shortcut> {
+    let query = query.trim();
+
+    if let Some((bang, terms)) = query.split_once(char::is_whitespace)
+        && let Some(engine) = engines.iter().find(|e| e.bang == bang)
+    {
+        let terms = terms.trim();
+        if terms.is_empty() {
+            return None;
+        }
+        return Some(Shortcut::Search {
+            engine: engine.name.clone(),
+            terms: terms.to_string(),
+            url: engine.url.replace("{}", &encode(terms)),
+        });
+    }
+
+    looks_like_url(query).then(|| {
+        if query.contains("://") {
+            Shortcut::Url(query.to_string())
+        } else {
+            Shortcut::Url(format!("https://{}", query))
+        }
+    })
+}
+
+/// `https://...`, `http://...`, or something like `www.rust-lang.org`, `docs.rs/ratatui` or
+/// `localhost:8080`. A bare dotted name like `github.com` or `notes.txt` is more likely what is
+/// being searched for, so it needs a `www.` prefix, a port or a path to count.
+fn looks_like_url(query: &str) -> bool {
+    if query.is_empty() || query.contains(char::is_whitespace) {
+        return false;
+    }
+    if query.starts_with("http://") || query.starts_with("https://") {
+        return true;
+    }
+
+    let authority = query.split(['/', '?', '#']).next().unwrap_or_default();
+    let (host, has_port) = match authority.rsplit_once(':') {
+        Some((host, port)) if !port.is_empty() && port.chars().all(|c| c.is_ascii_digit()) => (host, true),
+        _ => (authority, false),
+    };
+    let has_path = query.len() > authority.len();
+    if !(has_port || has_path || host.starts_with("www.")) {
+        return false;
+    }
+    if host == "localhost" {
+        return true;
+    }
+
+    // A dotted name ending in an alphabetic top-level domain, so `1.5` or `v1.2` aren't URLs
+    let labels: Vec<&str> = host.split('.').collect();
+    labels.len() >= 2
+        && labels.iter().all(|l| !l.is_empty() && l.chars().all(|c| c.is_alphanumeric() || c == '-'))
+        && labels.last().is_some_and(|tld| tld.len() >= 2 && tld.chars().all(char::is_alphabetic))
+}
+
+/// Percent-encode search terms for a query string
+fn encode(terms: &str) -> String {
+    let mut encoded = String::new();
+    for byte in terms.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => encoded.push(byte as char),
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn engines() -> Vec<SearchEngine> {
+        crate::config::test_config().web.engines
+    }
+
+    #[test]
+    fn test_bang_search() {
+        let Some(Shortcut::Search { engine, url, .. }) = parse("w tokio runtime", &engines()) else {
+            panic!("not a search");
+        };
+        assert_eq!(engine, "Wikipedia");
+        assert_eq!(url, "https://en.wikipedia.org/wiki/Special:Search?search=tokio%20runtime");
+
+        assert_eq!(parse("gh", &engines()), None);
+        assert_eq!(parse("xyz rust", &engines()), None);
+    }
+
+    #[test]
+    fn test_urls() {
+        assert_eq!(parse("docs.rs/ratatui", &engines()), Some(Shortcut::Url("https://docs.rs/ratatui".to_string())));
+        assert_eq!(parse("http://localhost:8080/x", &engines()), Some(Shortcut::Url("http://localhost:8080/x".to_string())));
+        assert_eq!(parse("localhost:3000", &engines()), Some(Shortcut::Url("https://localhost:3000".to_string())));
+        assert_eq!(parse("1.5", &engines()), None);
+        assert_eq!(parse("firefox", &engines()), None);
+        assert_eq!(parse("v1.2", &engines()), None);
+        assert_eq!(parse("www.rust-lang.org", &engines()), Some(Shortcut::Url("https://www.rust-lang.org".to_string())));
+        assert_eq!(parse("example.com:8443", &engines()), Some(Shortcut::Url("https://example.com:8443".to_string())));
+        // Bare names are searched for rather than opened
+        assert_eq!(parse("github.com", &engines()), None);
+        assert_eq!(parse("notes.txt", &engines()), None);
+        assert_eq!(parse("1.5/2", &engines()), None);
+    }
+
+    #[test]
+    fn test_encode() {
+        assert_eq!(encode("c++ & rust"), "c%2B%2B%20%26%20rust");
+        assert_eq!(encode("café"), "caf%C3%A9");
+    }
+}